@@ -0,0 +1,85 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Recording and deterministic replay of `ui-events` input streams.
+//!
+//! This crate captures a timestamped stream of pointer and keyboard events,
+//! stores it in a compact binary form, and plays it back later, either with
+//! its original timing or as fast as possible. It is intended for reproducing
+//! UI bugs from captured sessions, and for driving tests with real input.
+//!
+//! ## What it provides:
+//!
+//! - [`Recorder`]: builds a [`Recording`] from live [`PointerEvent`]s and [`KeyboardEvent`]s.
+//! - [`Recording`]: an ordered list of [`TimedEvent`]s, with [`Recording::to_bytes`] and
+//!   [`Recording::from_bytes`] for storage.
+//! - [`Replay`]: hands the recorded events back frame by frame.
+//!
+//! ## Time
+//!
+//! Recorded times use the same `u64` nanosecond clock as
+//! [`PointerState::time`](ui_events::pointer::PointerState::time). Keyboard
+//! events do not carry a time, so the caller provides one when recording them.
+//!
+//! ## Versioning
+//!
+//! Recordings start with a short header holding [`MAGIC`] and [`FORMAT_VERSION`].
+//! Recordings with a different format version are rejected with
+//! [`Error::UnsupportedVersion`] rather than being misread.
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_events_record::{RecordedEvent, Recorder, Recording, Replay};
+//! use ui_events::pointer::PointerEvent;
+//! use ui_input_state::InputState;
+//!
+//! // While capturing a session…
+//! let mut recorder = Recorder::new();
+//! # let event: PointerEvent = unimplemented!();
+//! recorder.record_pointer(&event);
+//! let bytes = recorder.finish().to_bytes();
+//!
+//! // …and later, replay it into an `InputState`, one 16ms frame at a time.
+//! let recording = Recording::from_bytes(&bytes).unwrap();
+//! let mut replay = Replay::new(&recording);
+//! let mut input = InputState::default();
+//! while !replay.is_finished() {
+//!     for e in replay.step(16_000_000) {
+//!         match &e.event {
+//!             RecordedEvent::Pointer(p) => input.primary_pointer.process_pointer_event(p.clone()),
+//!             RecordedEvent::Keyboard(k) => input.keyboard.process_keyboard_event(k.clone()),
+//!         }
+//!     }
+//!     // Run your UI update here.
+//!     input.clear_frame();
+//! }
+//! ```
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default): Use the Rust standard library.
+//! - `libm`: Enable `ui-events/libm`, for its floating point math in `no_std` environments.
+//!
+//! [`PointerEvent`]: ui_events::pointer::PointerEvent
+//! [`KeyboardEvent`]: ui_events::keyboard::KeyboardEvent
+// LINEBENDER LINT SET - lib.rs - v3
+// See https://linebender.org/wiki/canonical-lints/
+// These lints shouldn't apply to examples or tests.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+// These lints shouldn't apply to examples.
+#![warn(clippy::print_stdout, clippy::print_stderr)]
+// Targeting e.g. 32-bit means structs containing usize can give false positives for 64-bit.
+#![cfg_attr(target_pointer_width = "64", warn(clippy::trivially_copy_pass_by_ref))]
+// END LINEBENDER LINT SET
+#![no_std]
+
+extern crate alloc;
+
+mod recording;
+mod replay;
+
+pub use crate::recording::{
+    Error, FORMAT_VERSION, MAGIC, RecordedEvent, Recorder, Recording, TimedEvent,
+};
+pub use crate::replay::Replay;
@@ -0,0 +1,335 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Capturing and encoding input streams.
+//!
+//! A [`Recording`] is an ordered list of [`TimedEvent`]s, each carrying the
+//! nanosecond timestamp it was observed at. Use a [`Recorder`] to build one
+//! from live events, then [`Recording::to_bytes`] to store it.
+//!
+//! ## File layout
+//!
+//! | Bytes | Contents                                              |
+//! |-------|-------------------------------------------------------|
+//! | 0..8  | [`MAGIC`]                                             |
+//! | 8..10 | Format version, little endian (see [`FORMAT_VERSION`]) |
+//! | 10..  | `postcard` encoded list of [`TimedEvent`]s            |
+
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+use ui_events::keyboard::KeyboardEvent;
+use ui_events::pointer::{
+    PointerButtonEvent, PointerEvent, PointerGestureEvent, PointerScrollEvent, PointerUpdate,
+};
+
+/// Bytes identifying a recording.
+pub const MAGIC: [u8; 8] = *b"UIEVREC\0";
+
+/// Version of the encoding written by [`Recording::to_bytes`].
+///
+/// This is bumped whenever the encoded layout of recorded events changes,
+/// so that recordings made with an older version are rejected with
+/// [`Error::UnsupportedVersion`] rather than misread.
+pub const FORMAT_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = MAGIC.len() + 2;
+
+/// A recorded input event.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    /// A [`PointerEvent`].
+    Pointer(PointerEvent),
+    /// A [`KeyboardEvent`].
+    Keyboard(KeyboardEvent),
+}
+
+impl From<PointerEvent> for RecordedEvent {
+    fn from(event: PointerEvent) -> Self {
+        Self::Pointer(event)
+    }
+}
+
+impl From<KeyboardEvent> for RecordedEvent {
+    fn from(event: KeyboardEvent) -> Self {
+        Self::Keyboard(event)
+    }
+}
+
+/// A [`RecordedEvent`] along with the time it was observed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimedEvent {
+    /// `u64` nanoseconds, in the same time base as [`PointerState::time`].
+    ///
+    /// [`PointerState::time`]: ui_events::pointer::PointerState::time
+    pub time: u64,
+    /// The event.
+    pub event: RecordedEvent,
+}
+
+/// An ordered stream of recorded input events.
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    /// Events, ordered by `time`.
+    events: Vec<TimedEvent>,
+}
+
+impl Recording {
+    /// The recorded events, ordered by `time`.
+    pub fn events(&self) -> &[TimedEvent] {
+        &self.events
+    }
+
+    /// Returns `true` if nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Time of the first event, or `0` if the recording is empty.
+    pub fn start_time(&self) -> u64 {
+        self.events.first().map_or(0, |e| e.time)
+    }
+
+    /// Time between the first and last events, in nanoseconds.
+    pub fn duration(&self) -> u64 {
+        self.events
+            .last()
+            .map_or(0, |e| e.time.saturating_sub(self.start_time()))
+    }
+
+    /// Encode the recording, including the versioned header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN);
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        postcard::to_extend(&self.events, out).expect("writing to a Vec cannot fail")
+    }
+
+    /// Decode a recording produced by [`Recording::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < HEADER_LEN {
+            return Err(Error::Truncated);
+        }
+        let (header, body) = bytes.split_at(HEADER_LEN);
+        if header[..MAGIC.len()] != MAGIC {
+            return Err(Error::BadMagic);
+        }
+        let version = u16::from_le_bytes([header[MAGIC.len()], header[MAGIC.len() + 1]]);
+        if version != FORMAT_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let events = postcard::from_bytes(body).map_err(Error::Decode)?;
+        Ok(Self { events })
+    }
+}
+
+/// Captures events into a [`Recording`].
+///
+/// Pointer events are stamped with their own [`PointerState::time`] where
+/// they carry one. Events that do not carry a time, such as
+/// [`PointerEvent::Leave`], are stamped with the most recent time seen.
+///
+/// [`PointerState::time`]: ui_events::pointer::PointerState::time
+#[derive(Clone, Debug, Default)]
+pub struct Recorder {
+    /// The recording under construction.
+    recording: Recording,
+    /// Time of the last recorded event.
+    last_time: u64,
+}
+
+impl Recorder {
+    /// Create a new, empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a [`PointerEvent`], taking its time from the event where possible.
+    pub fn record_pointer(&mut self, event: &PointerEvent) {
+        let time = match event {
+            PointerEvent::Down(PointerButtonEvent { state, .. })
+            | PointerEvent::Up(PointerButtonEvent { state, .. })
+            | PointerEvent::Move(PointerUpdate { current: state, .. })
+            | PointerEvent::Scroll(PointerScrollEvent { state, .. })
+            | PointerEvent::Gesture(PointerGestureEvent { state, .. }) => state.time,
+            PointerEvent::Cancel(_) | PointerEvent::Enter(_) | PointerEvent::Leave(_) => {
+                self.last_time
+            }
+        };
+        self.record(time, event.clone());
+    }
+
+    /// Record a [`KeyboardEvent`] observed at `time`.
+    ///
+    /// `time` should use the same time base as pointer events.
+    pub fn record_keyboard(&mut self, time: u64, event: &KeyboardEvent) {
+        self.record(time, event.clone());
+    }
+
+    /// Record an event observed at `time`.
+    ///
+    /// Times that go backwards are clamped to the previous event's time so
+    /// that the recording stays ordered.
+    pub fn record(&mut self, time: u64, event: impl Into<RecordedEvent>) {
+        let time = time.max(self.last_time);
+        self.last_time = time;
+        self.recording.events.push(TimedEvent {
+            time,
+            event: event.into(),
+        });
+    }
+
+    /// The events recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Finish recording.
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// An error decoding a [`Recording`].
+#[derive(Debug)]
+pub enum Error {
+    /// The data is too short to contain a header.
+    Truncated,
+    /// The data does not start with [`MAGIC`].
+    BadMagic,
+    /// The recording was written with an unsupported format version.
+    UnsupportedVersion(u16),
+    /// The event data could not be decoded.
+    Decode(postcard::Error),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("recording is truncated"),
+            Self::BadMagic => f.write_str("not a ui-events recording"),
+            Self::UnsupportedVersion(v) => write!(
+                f,
+                "unsupported recording format version {v} (expected {FORMAT_VERSION})"
+            ),
+            Self::Decode(e) => write!(f, "failed to decode recording: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use dpi::PhysicalPosition;
+    use ui_events::keyboard::{Code, Key, KeyState, Location, Modifiers};
+    use ui_events::pointer::{PointerButton, PointerId, PointerInfo, PointerState, PointerType};
+
+    const MOUSE: PointerInfo = PointerInfo {
+        pointer_id: Some(PointerId::PRIMARY),
+        persistent_device_id: None,
+        pointer_type: PointerType::Mouse,
+    };
+
+    fn down(time: u64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: MOUSE,
+            state: PointerState {
+                time,
+                position: PhysicalPosition { x: 3.0, y: 4.0 },
+                buttons: PointerButton::Primary.into(),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn key(state: KeyState) -> KeyboardEvent {
+        KeyboardEvent {
+            state,
+            key: Key::Character("z".into()),
+            location: Location::Standard,
+            code: Code::KeyZ,
+            modifiers: Modifiers::CONTROL,
+            is_composing: false,
+            repeat: false,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut recorder = Recorder::new();
+        recorder.record_pointer(&down(10));
+        recorder.record_keyboard(20, &key(KeyState::Down));
+        recorder.record_pointer(&PointerEvent::Leave(MOUSE));
+        let recording = recorder.finish();
+
+        let decoded = Recording::from_bytes(&recording.to_bytes()).unwrap();
+        assert_eq!(decoded.events().len(), 3);
+        assert_eq!(decoded.start_time(), 10);
+        assert_eq!(decoded.duration(), 10);
+
+        let TimedEvent {
+            time: 10,
+            event: RecordedEvent::Pointer(PointerEvent::Down(e)),
+        } = &decoded.events()[0]
+        else {
+            panic!(
+                "expected a pointer down at 10, got {:?}",
+                decoded.events()[0]
+            );
+        };
+        assert_eq!(e.state.position, PhysicalPosition { x: 3.0, y: 4.0 });
+        assert!(e.state.buttons.contains(PointerButton::Primary));
+
+        let TimedEvent {
+            time: 20,
+            event: RecordedEvent::Keyboard(k),
+        } = &decoded.events()[1]
+        else {
+            panic!("expected a key at 20, got {:?}", decoded.events()[1]);
+        };
+        assert_eq!(k, &key(KeyState::Down));
+
+        // `Leave` carries no time, so it takes the last seen time.
+        assert_eq!(decoded.events()[2].time, 20);
+    }
+
+    #[test]
+    fn time_never_goes_backwards() {
+        let mut recorder = Recorder::new();
+        recorder.record_pointer(&down(50));
+        recorder.record_pointer(&down(40));
+        assert_eq!(recorder.recording().events()[1].time, 50);
+    }
+
+    #[test]
+    fn rejects_bad_header() {
+        assert!(matches!(
+            Recording::from_bytes(b"UIEV"),
+            Err(Error::Truncated)
+        ));
+        assert!(matches!(
+            Recording::from_bytes(b"NOTAREC\0\x01\x00\x00"),
+            Err(Error::BadMagic)
+        ));
+
+        let mut bytes = Recording::default().to_bytes();
+        bytes[MAGIC.len()..HEADER_LEN].copy_from_slice(&2_u16.to_le_bytes());
+        assert!(matches!(
+            Recording::from_bytes(&bytes),
+            Err(Error::UnsupportedVersion(2))
+        ));
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.push(0xff);
+        assert!(matches!(
+            Recording::from_bytes(&bytes),
+            Err(Error::Decode(_))
+        ));
+    }
+}
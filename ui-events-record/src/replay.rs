@@ -0,0 +1,194 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Frame-by-frame playback of a [`Recording`].
+//!
+//! [`Replay`] hands out the recorded events in order, one frame at a time,
+//! against a clock measured in nanoseconds since the start of the recording.
+//!
+//! - To replay with the original timing, pass the real time elapsed since
+//!   playback started to [`Replay::advance_to`] each frame.
+//! - To replay as fast as possible, either call [`Replay::step`] with a fixed
+//!   frame duration in a loop, which keeps the original grouping of events
+//!   into frames, or take everything at once with [`Replay::remaining`].
+//!
+//! Both are deterministic: the same recording and the same sequence of
+//! calls always yields the same events in the same frames.
+use crate::{Recording, TimedEvent};
+
+/// Plays back a [`Recording`].
+#[derive(Clone, Debug)]
+pub struct Replay<'a> {
+    /// All recorded events.
+    events: &'a [TimedEvent],
+    /// Index of the next event to hand out.
+    cursor: usize,
+    /// Time of the first event.
+    start: u64,
+    /// Playback clock, relative to `start`.
+    elapsed: u64,
+}
+
+impl<'a> Replay<'a> {
+    /// Start playback of `recording` at its beginning.
+    pub fn new(recording: &'a Recording) -> Self {
+        Self {
+            events: recording.events(),
+            cursor: 0,
+            start: recording.start_time(),
+            elapsed: 0,
+        }
+    }
+
+    /// Current playback time, in nanoseconds since the start of the recording.
+    pub fn elapsed(&self) -> u64 {
+        self.elapsed
+    }
+
+    /// Returns `true` once every event has been handed out.
+    pub fn is_finished(&self) -> bool {
+        self.cursor == self.events.len()
+    }
+
+    /// Playback time at which the next event is due, if any.
+    ///
+    /// This is useful for scheduling a wakeup when replaying with the
+    /// original timing.
+    pub fn next_due(&self) -> Option<u64> {
+        self.events
+            .get(self.cursor)
+            .map(|e| e.time.saturating_sub(self.start))
+    }
+
+    /// Move the playback clock to `elapsed` nanoseconds since the start of
+    /// the recording, and return the events that became due.
+    ///
+    /// The clock never moves backwards; an earlier `elapsed` returns nothing.
+    pub fn advance_to(&mut self, elapsed: u64) -> &'a [TimedEvent] {
+        self.elapsed = self.elapsed.max(elapsed);
+        let first = self.cursor;
+        let due = self.events[first..]
+            .iter()
+            .take_while(|e| e.time.saturating_sub(self.start) <= self.elapsed)
+            .count();
+        self.cursor += due;
+        &self.events[first..self.cursor]
+    }
+
+    /// Advance the playback clock by `frame` nanoseconds, and return the
+    /// events that became due.
+    pub fn step(&mut self, frame: u64) -> &'a [TimedEvent] {
+        self.advance_to(self.elapsed.saturating_add(frame))
+    }
+
+    /// Return every event not yet handed out, finishing playback.
+    pub fn remaining(&mut self) -> &'a [TimedEvent] {
+        let first = self.cursor;
+        self.cursor = self.events.len();
+        if let Some(last) = self.events.last() {
+            self.elapsed = self.elapsed.max(last.time.saturating_sub(self.start));
+        }
+        &self.events[first..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Recorder;
+    use ui_events::pointer::{
+        PointerButton, PointerButtonEvent, PointerEvent, PointerId, PointerInfo, PointerState,
+        PointerType,
+    };
+    use ui_input_state::InputState;
+
+    fn button(time: u64, down: bool) -> PointerEvent {
+        let e = PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: PointerInfo {
+                pointer_id: Some(PointerId::PRIMARY),
+                persistent_device_id: None,
+                pointer_type: PointerType::Mouse,
+            },
+            state: PointerState {
+                time,
+                buttons: if down {
+                    PointerButton::Primary.into()
+                } else {
+                    Default::default()
+                },
+                ..Default::default()
+            },
+        };
+        if down {
+            PointerEvent::Down(e)
+        } else {
+            PointerEvent::Up(e)
+        }
+    }
+
+    fn recording() -> Recording {
+        let mut recorder = Recorder::new();
+        recorder.record_pointer(&button(1_000, true));
+        recorder.record_pointer(&button(5_000, false));
+        recorder.record_pointer(&button(21_000, true));
+        recorder.finish()
+    }
+
+    #[test]
+    fn original_timing() {
+        let recording = recording();
+        let mut replay = Replay::new(&recording);
+
+        assert_eq!(replay.next_due(), Some(0));
+        assert_eq!(replay.advance_to(0).len(), 1);
+        assert_eq!(replay.next_due(), Some(4_000));
+        assert!(replay.advance_to(3_999).is_empty());
+        assert_eq!(replay.advance_to(4_000).len(), 1);
+        // The clock does not go backwards.
+        assert!(replay.advance_to(0).is_empty());
+        assert_eq!(replay.elapsed(), 4_000);
+        assert!(!replay.is_finished());
+        assert_eq!(replay.advance_to(100_000).len(), 1);
+        assert!(replay.is_finished());
+        assert_eq!(replay.next_due(), None);
+    }
+
+    #[test]
+    fn fixed_steps_feed_input_state() {
+        let recording = recording();
+        let mut replay = Replay::new(&recording);
+        let mut input = InputState::default();
+
+        let mut frames = 0;
+        let mut pressed_frames = 0;
+        while !replay.is_finished() {
+            for e in replay.step(8_000) {
+                if let crate::RecordedEvent::Pointer(p) = &e.event {
+                    input.primary_pointer.process_pointer_event(p.clone());
+                }
+            }
+            if input.primary_pointer.is_primary_just_pressed() {
+                pressed_frames += 1;
+            }
+            input.clear_frame();
+            frames += 1;
+        }
+
+        // Frames end at 8µs, 16µs, 24µs since the first event.
+        assert_eq!(frames, 3);
+        assert_eq!(pressed_frames, 2);
+        assert!(input.primary_pointer.is_down(PointerButton::Primary));
+    }
+
+    #[test]
+    fn remaining_drains() {
+        let recording = recording();
+        let mut replay = Replay::new(&recording);
+        replay.step(0);
+        assert_eq!(replay.remaining().len(), 2);
+        assert!(replay.is_finished());
+        assert_eq!(replay.elapsed(), 20_000);
+        assert!(replay.remaining().is_empty());
+    }
+}
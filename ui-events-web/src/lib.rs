@@ -2,22 +2,31 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! This crate bridges [`web_sys`] DOM input events — Pointer Events (mouse, touch, pen),
-//! Wheel, and Keyboard — into the [`ui-events`] model.
+//! Wheel, Keyboard, and Composition/Input — into the [`ui-events`] model.
 //!
 //! It provides lightweight helpers to convert browser events into portable
 //! `ui-events` types you can feed into your input handling. It supports
-//! Pointer Events (mouse, touch, pen) and keyboard.
+//! Pointer Events (mouse, touch, pen), keyboard, and text composition.
 //!
 //! ## Keyboard
 //!
 //! - [`keyboard::from_web_keyboard_event`]
 //! - Optional helpers: [`keyboard::from_web_keydown_event`], [`keyboard::from_web_keyup_event`]
 //!
+//! ## Text input and composition
+//!
+//! - [`text_input::ime_events_from_composition_event`] for `compositionstart`,
+//!   `compositionupdate`, and `compositionend`
+//! - [`text_input::from_web_input_event`] for `beforeinput` and `input`, including
+//!   the `inputType` and data
+//!
 //! ## Pointer (Pointer Events)
 //!
 //! - One‑shot DOM conversion: [`pointer::pointer_event_from_dom_event`]
 //! - Multi-touch aware DOM conversion (may return multiple events):
 //!   [`pointer::pointer_events_from_dom_event`]
+//! - Click and tap counting for all pointer types:
+//!   [`pointer::pointer_events_from_dom_event_with_counter`]
 //! - Per‑event helpers (preferred):
 //!   [`pointer::down_from_pointer_event`], [`pointer::up_from_pointer_event`],
 //!   [`pointer::move_from_pointer_event`], [`pointer::enter_from_pointer_event`],
@@ -29,6 +38,13 @@
 //! - Conversion options: [`pointer::Options`] (controls scale/coalesced/predicted)
 //! - Pointer capture helpers: [`pointer::set_pointer_capture`],
 //!   [`pointer::release_pointer_capture`], [`pointer::has_pointer_capture`]
+//! - Relative motion from `movementX`/`movementY`, for mouse-look and the like:
+//!   [`pointer::relative_motion_from_dom_event`],
+//!   [`pointer::relative_motion_from_pointer_event`],
+//!   [`pointer::relative_motion_from_mouse_event`]
+//! - Pointer lock helpers: [`pointer::request_pointer_lock`],
+//!   [`pointer::exit_pointer_lock`], [`pointer::has_pointer_lock`], and
+//!   [`pointer::PointerLockObserver`] for `pointerlockchange`
 //!
 //! ## Notes
 //!
@@ -56,10 +72,15 @@
 //!   - `pressure`, `tangential_pressure`, `contact_geometry`, and `orientation` are populated
 //!     from Pointer Events data when available (preferring `altitudeAngle`/`azimuthAngle`,
 //!     otherwise falling back to `tiltX`/`tiltY`).
-//!   - Stylus rotation/twist (Pointer Events `twist`) is not currently exposed by `ui-events`,
-//!     so it is not mapped.
+//!   - Stylus rotation (Pointer Events `twist`) is mapped to `PointerOrientation::twist`.
 //! - Keyboard: unknown `key`/`code` map to `Unidentified`; `is_composing` reflects the DOM flag.
 //!
+//! ## Features
+//!
+//! - `std` (enabled by default): Use the Rust standard library.
+//! - `libm`: Use `libm` for floating point math in `no_std` environments,
+//!   by enabling `ui-events/libm`.
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -108,7 +129,11 @@
 // END LINEBENDER LINT SET
 #![no_std]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("ui-events-web requires either the `std` or `libm` feature");
+
 extern crate alloc;
 
 pub mod keyboard;
 pub mod pointer;
+pub mod text_input;
@@ -3,21 +3,24 @@
 
 //! Support routines for converting pointer data from [`web_sys`].
 
+use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
 
 use dpi::{PhysicalPosition, PhysicalSize};
 use js_sys::{Array, Function, Reflect};
-use ui_events::ScrollDelta;
 use ui_events::keyboard::Modifiers;
 use ui_events::pointer::{
-    PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerId, PointerInfo,
-    PointerOrientation, PointerState, PointerType, PointerUpdate,
+    ClickCounter, MotionDelta, PointerButton, PointerButtonEvent, PointerButtons, PointerEvent,
+    PointerId, PointerInfo, PointerOrientation, PointerState, PointerType, PointerUpdate,
+    RelativeMotionEvent,
 };
+use ui_events::{ScrollDelta, ScrollPhase, ScrollSource};
+use web_sys::wasm_bindgen::closure::Closure;
 use web_sys::wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    Element, Event, MouseEvent, PointerEvent as WebPointerEvent, Touch, TouchEvent, TouchList,
-    WheelEvent,
+    Document, Element, Event, MouseEvent, PointerEvent as WebPointerEvent, Touch, TouchEvent,
+    TouchList, WheelEvent,
 };
 
 #[inline]
@@ -260,6 +263,11 @@ pub fn leave_from_mouse_event(_e: &MouseEvent) -> PointerEvent {
 /// Build a `Scroll` from a DOM `wheel` event.
 ///
 /// `scale_factor` controls conversion of CSS pixel deltas to physical pixels.
+///
+/// DOM wheel events have no notion of gestures, so the phase is always
+/// [`ScrollPhase::Changed`]. The source is a best-effort guess: line and page
+/// deltas are from a [`ScrollSource::Wheel`], and fractional pixel deltas are
+/// from a [`ScrollSource::Touchpad`]; whole pixel deltas could be either.
 pub fn scroll_from_wheel_event(e: &WheelEvent, scale_factor: f64) -> PointerEvent {
     let delta = match e.delta_mode() {
         WheelEvent::DOM_DELTA_PIXEL => ScrollDelta::PixelDelta(PhysicalPosition {
@@ -279,10 +287,26 @@ pub fn scroll_from_wheel_event(e: &WheelEvent, scale_factor: f64) -> PointerEven
     PointerEvent::Scroll(ui_events::pointer::PointerScrollEvent {
         pointer: pointer_info_mouse(),
         delta,
+        phase: ScrollPhase::Changed,
+        source: scroll_source_from_wheel(e.delta_mode(), e.delta_x(), e.delta_y()),
         state: state_from_mouse_event(me, scale_factor),
     })
 }
 
+/// Guess the kind of device behind a DOM `wheel` event.
+///
+/// Line and page deltas only come from wheels. Pixel deltas come from both, but
+/// fractional deltas are only produced by touchpads and other precise devices.
+fn scroll_source_from_wheel(delta_mode: u32, delta_x: f64, delta_y: f64) -> ScrollSource {
+    match delta_mode {
+        WheelEvent::DOM_DELTA_LINE | WheelEvent::DOM_DELTA_PAGE => ScrollSource::Wheel,
+        WheelEvent::DOM_DELTA_PIXEL if delta_x % 1.0 != 0.0 || delta_y % 1.0 != 0.0 => {
+            ScrollSource::Touchpad
+        }
+        _ => ScrollSource::Unknown,
+    }
+}
+
 // PointerEvent (Web) conversions
 
 fn pointer_type_from_str(s: &str) -> PointerType {
@@ -327,6 +351,8 @@ fn modifiers_from_pointer(e: &WebPointerEvent) -> Modifiers {
 }
 
 fn orientation_from_pointer_event(e: &WebPointerEvent) -> PointerOrientation {
+    let twist = twist_from_degrees(e.twist());
+
     // Prefer Pointer Events Level 3 altitude/azimuth when present (radians).
     let obj = e.as_ref();
     if let (Ok(alt), Ok(azi)) = (
@@ -341,36 +367,25 @@ fn orientation_from_pointer_event(e: &WebPointerEvent) -> PointerOrientation {
             return PointerOrientation {
                 altitude: alt as f32,
                 azimuth: azi as f32,
+                twist,
             };
         }
     }
 
-    // Fall back to Pointer Events tiltX/tiltY (degrees).
-    // tiltX/tiltY are in [-90, 90]. Avoid tan() singularities at 90 degrees.
-    let tilt_x = (e.tilt_x() as f32).clamp(-89.9, 89.9);
-    let tilt_y = (e.tilt_y() as f32).clamp(-89.9, 89.9);
-    pointer_orientation_from_tilt_degrees(tilt_x, tilt_y)
+    // Fall back to Pointer Events tiltX/tiltY (degrees, in [-90, 90]).
+    PointerOrientation {
+        twist,
+        ..pointer_orientation_from_tilt_degrees(e.tilt_x() as f32, e.tilt_y() as f32)
+    }
 }
 
 fn pointer_orientation_from_tilt_degrees(tilt_x_deg: f32, tilt_y_deg: f32) -> PointerOrientation {
-    let tx = tilt_x_deg.to_radians();
-    let ty = tilt_y_deg.to_radians();
-    let x = tx.tan();
-    let y = ty.tan();
-
-    // Model the pen axis as the normalized vector (x, y, 1), where x/z = tan(tiltX),
-    // y/z = tan(tiltY). When perpendicular: (0,0,1).
-    let inv_norm = 1.0 / (x.mul_add(x, y * y) + 1.0).sqrt();
-    let z = inv_norm;
-
-    let altitude = z.asin();
-    let azimuth = if x == 0.0 && y == 0.0 {
-        core::f32::consts::FRAC_PI_2
-    } else {
-        y.atan2(x)
-    };
+    PointerOrientation::from_tilt(tilt_x_deg.to_radians(), tilt_y_deg.to_radians())
+}
 
-    PointerOrientation { altitude, azimuth }
+/// Convert Pointer Events `twist` (clockwise degrees in `[0, 359]`) to radians in `[0, 2π)`.
+fn twist_from_degrees(twist_deg: i32) -> f32 {
+    (twist_deg.rem_euclid(360) as f32).to_radians()
 }
 
 /// Build a [`PointerState`] from a DOM [`web_sys::PointerEvent`].
@@ -379,9 +394,7 @@ fn pointer_orientation_from_tilt_degrees(tilt_x_deg: f32, tilt_y_deg: f32) -> Po
 ///   physical pixels.
 /// - Uses the event's reported `pressure`, `tangentialPressure`, `width/height`, and
 ///   stylus orientation where available (preferring `altitudeAngle`/`azimuthAngle`,
-///   otherwise falling back to `tiltX`/`tiltY`).
-/// - Pointer Events `twist` is not currently mapped (there is no corresponding field in
-///   `ui-events`).
+///   otherwise falling back to `tiltX`/`tiltY`), including `twist`.
 pub fn state_from_pointer_event(e: &WebPointerEvent, scale_factor: f64) -> PointerState {
     let css_x = e.client_x() as f64;
     let css_y = e.client_y() as f64;
@@ -560,6 +573,55 @@ pub fn cancel_from_pointer_event(e: &WebPointerEvent) -> PointerEvent {
     PointerEvent::Cancel(pointer_info_from_web_pointer(e))
 }
 
+/// Make a [`MotionDelta::PixelDelta`] from DOM `movementX`/`movementY`.
+///
+/// `scale_factor` controls conversion of CSS pixel deltas to physical pixels.
+fn motion_delta_from_movement(movement_x: i32, movement_y: i32, scale_factor: f64) -> MotionDelta {
+    MotionDelta::PixelDelta(PhysicalPosition {
+        x: movement_x as f64 * scale_factor,
+        y: movement_y as f64 * scale_factor,
+    })
+}
+
+/// Build a [`RelativeMotionEvent`] from the `movementX`/`movementY` of a DOM `mousemove`.
+///
+/// Prefer [`relative_motion_from_pointer_event`] when handling W3C Pointer Events.
+pub fn relative_motion_from_mouse_event(e: &MouseEvent, scale_factor: f64) -> RelativeMotionEvent {
+    RelativeMotionEvent {
+        pointer: pointer_info_mouse(),
+        delta: motion_delta_from_movement(e.movement_x(), e.movement_y(), scale_factor),
+        state: state_from_mouse_event(e, scale_factor),
+    }
+}
+
+/// Build a [`RelativeMotionEvent`] from the `movementX`/`movementY` of a DOM `pointermove`.
+///
+/// The delta is a [`MotionDelta::PixelDelta`], with `scale_factor` converting CSS pixels
+/// to physical pixels. Movement keeps being reported while the pointer is locked
+/// (see [`request_pointer_lock`]), when the position in the state no longer changes.
+pub fn relative_motion_from_pointer_event(
+    e: &WebPointerEvent,
+    scale_factor: f64,
+) -> RelativeMotionEvent {
+    RelativeMotionEvent {
+        pointer: pointer_info_from_web_pointer(e),
+        delta: motion_delta_from_movement(e.movement_x(), e.movement_y(), scale_factor),
+        state: state_from_pointer_event(e, scale_factor),
+    }
+}
+
+/// Convert a DOM `pointermove` or `mousemove` into a [`RelativeMotionEvent`].
+///
+/// Returns `None` for other events.
+pub fn relative_motion_from_dom_event(ev: &Event, opts: &Options) -> Option<RelativeMotionEvent> {
+    if let Some(pe) = ev.dyn_ref::<WebPointerEvent>() {
+        return (pe.type_() == "pointermove")
+            .then(|| relative_motion_from_pointer_event(pe, opts.scale_factor));
+    }
+    let me = ev.dyn_ref::<MouseEvent>()?;
+    (me.type_() == "mousemove").then(|| relative_motion_from_mouse_event(me, opts.scale_factor))
+}
+
 /// Convert a DOM `TouchEvent` into zero or more `ui-events` [`PointerEvent`]s.
 ///
 /// Browser touch events can report multiple changed touches at once, so this returns a `Vec`.
@@ -572,7 +634,6 @@ pub fn pointer_events_from_touch_event(ev: &TouchEvent, opts: &Options) -> Vec<P
     let time_ns = ms_to_ns_u64(ev.time_stamp());
     let modifiers = modifiers_from_touch(ev);
 
-    let touch_count = pointer_attach_count_from_active_touches(ev.touches().length());
     let primary_identifier = min_touch_identifier_from_event(ev);
 
     let type_ = ev.type_();
@@ -589,30 +650,18 @@ pub fn pointer_events_from_touch_event(ev: &TouchEvent, opts: &Options) -> Vec<P
             "touchstart" => out.push(PointerEvent::Down(PointerButtonEvent {
                 button: None,
                 pointer,
-                state: state_from_touch(&touch, time_ns, modifiers, touch_count, opts.scale_factor),
+                state: state_from_touch(&touch, time_ns, modifiers, opts.scale_factor),
             })),
             "touchmove" => out.push(PointerEvent::Move(PointerUpdate {
                 pointer,
-                current: state_from_touch(
-                    &touch,
-                    time_ns,
-                    modifiers,
-                    touch_count,
-                    opts.scale_factor,
-                ),
+                current: state_from_touch(&touch, time_ns, modifiers, opts.scale_factor),
                 coalesced: Vec::new(),
                 predicted: Vec::new(),
             })),
             "touchend" => out.push(PointerEvent::Up(PointerButtonEvent {
                 button: None,
                 pointer,
-                state: state_from_touch_end(
-                    &touch,
-                    time_ns,
-                    modifiers,
-                    touch_count,
-                    opts.scale_factor,
-                ),
+                state: state_from_touch_end(&touch, time_ns, modifiers, opts.scale_factor),
             })),
             "touchcancel" => out.push(PointerEvent::Cancel(pointer)),
             _ => {}
@@ -678,10 +727,6 @@ fn pointer_id_from_touch_identifier(id: i32, primary_identifier: Option<u64>) ->
     PointerId::new(id_u64.saturating_add(2))
 }
 
-fn pointer_attach_count_from_active_touches(active_touches: u32) -> u8 {
-    active_touches.min(255) as u8
-}
-
 fn pointer_info_from_touch(touch: &Touch, primary_identifier: Option<u64>) -> PointerInfo {
     PointerInfo {
         pointer_id: pointer_id_from_touch_identifier(touch.identifier(), primary_identifier),
@@ -694,7 +739,6 @@ fn state_from_touch(
     touch: &Touch,
     time_ns: u64,
     modifiers: Modifiers,
-    touch_count: u8,
     scale_factor: f64,
 ) -> PointerState {
     let css_x = touch.client_x() as f64;
@@ -717,7 +761,8 @@ fn state_from_touch(
         },
         buttons: PointerButtons::default(),
         modifiers,
-        count: touch_count,
+        // Touch events carry no tap count; see `pointer_events_from_dom_event_with_counter`.
+        count: 0,
         contact_geometry: PhysicalSize {
             width: width_css * scale_factor,
             height: height_css * scale_factor,
@@ -733,10 +778,9 @@ fn state_from_touch_end(
     touch: &Touch,
     time_ns: u64,
     modifiers: Modifiers,
-    touch_count: u8,
     scale_factor: f64,
 ) -> PointerState {
-    let mut s = state_from_touch(touch, time_ns, modifiers, touch_count, scale_factor);
+    let mut s = state_from_touch(touch, time_ns, modifiers, scale_factor);
     s.pressure = 0.0;
     s
 }
@@ -784,6 +828,23 @@ pub fn pointer_events_from_dom_event(ev: &Event, opts: &Options) -> Vec<PointerE
     Vec::new()
 }
 
+/// Like [`pointer_events_from_dom_event`], but with [`PointerState::count`] computed by
+/// `counter`, rather than taken from the DOM.
+///
+/// The DOM only reports click counts for mouse events, in `detail`, so use this to get
+/// tap counts for touch and pen that are consistent with other backends.
+/// Keep one [`ClickCounter`] per element you listen on.
+pub fn pointer_events_from_dom_event_with_counter(
+    ev: &Event,
+    opts: &Options,
+    counter: &mut ClickCounter,
+) -> Vec<PointerEvent> {
+    pointer_events_from_dom_event(ev, opts)
+        .into_iter()
+        .map(|e| counter.attach_count(e))
+        .collect()
+}
+
 /// Convert a DOM event (Mouse/Pointer/Wheel) into a `ui-events` [`PointerEvent`]
 /// with options to control conversion.
 ///
@@ -821,6 +882,106 @@ pub fn has_pointer_capture(el: &Element, e: &WebPointerEvent) -> bool {
     el.has_pointer_capture(e.pointer_id())
 }
 
+/// Request pointer lock on an element.
+///
+/// Browsers only grant this in response to a user gesture, such as a click, and
+/// do so asynchronously; observe the outcome with a [`PointerLockObserver`].
+/// While locked, the cursor is hidden and stays in place, and motion is only
+/// reported through [`relative_motion_from_pointer_event`].
+pub fn request_pointer_lock(el: &Element) {
+    el.request_pointer_lock();
+}
+
+/// Exit pointer lock, if any element of the document holds it.
+pub fn exit_pointer_lock(doc: &Document) {
+    doc.exit_pointer_lock();
+}
+
+/// Query whether an element currently holds pointer lock.
+pub fn has_pointer_lock(el: &Element) -> bool {
+    el.owner_document()
+        .and_then(|doc| doc.pointer_lock_element())
+        .is_some_and(|locked| locked == *el)
+}
+
+/// Observes `pointerlockchange` on a document, until dropped.
+#[derive(Debug)]
+pub struct PointerLockObserver {
+    /// Document the listener is registered on.
+    document: Document,
+    /// The registered listener.
+    listener: Closure<dyn FnMut(Event)>,
+}
+
+impl PointerLockObserver {
+    /// Call `f` with the element holding pointer lock, or `None` once it is released,
+    /// every time pointer lock changes on `doc`.
+    ///
+    /// This includes the user exiting pointer lock, for example by pressing Escape.
+    pub fn new(
+        doc: &Document,
+        mut f: impl FnMut(Option<Element>) + 'static,
+    ) -> Result<Self, JsValue> {
+        let document = doc.clone();
+        let listener = Closure::wrap(Box::new({
+            let document = document.clone();
+            move |_: Event| f(document.pointer_lock_element())
+        }) as Box<dyn FnMut(Event)>);
+        doc.add_event_listener_with_callback(
+            "pointerlockchange",
+            listener.as_ref().unchecked_ref(),
+        )?;
+        Ok(Self { document, listener })
+    }
+}
+
+impl Drop for PointerLockObserver {
+    fn drop(&mut self) {
+        let _ = self.document.remove_event_listener_with_callback(
+            "pointerlockchange",
+            self.listener.as_ref().unchecked_ref(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod wheel_tests {
+    use super::*;
+
+    #[test]
+    fn wheel_source_heuristic() {
+        assert_eq!(
+            scroll_source_from_wheel(WheelEvent::DOM_DELTA_LINE, 0.0, 3.0),
+            ScrollSource::Wheel
+        );
+        assert_eq!(
+            scroll_source_from_wheel(WheelEvent::DOM_DELTA_PAGE, 0.0, 1.0),
+            ScrollSource::Wheel
+        );
+        assert_eq!(
+            scroll_source_from_wheel(WheelEvent::DOM_DELTA_PIXEL, 0.0, 2.5),
+            ScrollSource::Touchpad
+        );
+        assert_eq!(
+            scroll_source_from_wheel(WheelEvent::DOM_DELTA_PIXEL, 0.0, 100.0),
+            ScrollSource::Unknown
+        );
+    }
+}
+
+#[cfg(test)]
+mod relative_motion_tests {
+    use super::*;
+
+    #[test]
+    fn movement_is_scaled_to_physical_pixels() {
+        assert_eq!(
+            motion_delta_from_movement(3, -4, 2.0),
+            MotionDelta::PixelDelta(PhysicalPosition { x: 6.0, y: -8.0 })
+        );
+    }
+}
+
 #[cfg(test)]
 mod touch_tests {
     use super::*;
@@ -845,15 +1006,6 @@ mod touch_tests {
         );
         assert_eq!(pointer_id_from_touch_identifier(-1, Some(0)), None);
     }
-
-    #[test]
-    fn touch_count_clamps_to_u8() {
-        assert_eq!(pointer_attach_count_from_active_touches(0), 0);
-        assert_eq!(pointer_attach_count_from_active_touches(1), 1);
-        assert_eq!(pointer_attach_count_from_active_touches(255), 255);
-        assert_eq!(pointer_attach_count_from_active_touches(256), 255);
-        assert_eq!(pointer_attach_count_from_active_touches(u32::MAX), 255);
-    }
 }
 
 #[cfg(test)]
@@ -902,9 +1054,9 @@ mod stylus_orientation_tests {
         let o = pointer_orientation_from_tilt_degrees(0.0, 30.0);
         assert_azimuth_approx(o.azimuth, core::f32::consts::FRAC_PI_2, 1e-6);
 
-        // Negative Y => azimuth ~ -pi/2
+        // Negative Y => azimuth ~ 3pi/2, as with `azimuthAngle`
         let o = pointer_orientation_from_tilt_degrees(0.0, -30.0);
-        assert_azimuth_approx(o.azimuth, -core::f32::consts::FRAC_PI_2, 1e-6);
+        assert_approx(o.azimuth, 3.0 * core::f32::consts::FRAC_PI_2, 1e-6);
     }
 
     #[test]
@@ -942,4 +1094,23 @@ mod stylus_orientation_tests {
         assert!(o.azimuth.is_finite());
         assert!(o.altitude < 0.01);
     }
+
+    #[test]
+    fn full_tilt_is_parallel() {
+        let o = pointer_orientation_from_tilt_degrees(90.0, 0.0);
+        assert!(o.altitude.abs() < 1e-6);
+        assert_azimuth_approx(o.azimuth, 0.0, 1e-6);
+
+        let o = pointer_orientation_from_tilt_degrees(0.0, -90.0);
+        assert!(o.altitude.abs() < 1e-6);
+        assert_azimuth_approx(o.azimuth, -core::f32::consts::FRAC_PI_2, 1e-6);
+    }
+
+    #[test]
+    fn twist_degrees_map_to_radians() {
+        assert_eq!(twist_from_degrees(0), 0.0);
+        assert_approx(twist_from_degrees(90), core::f32::consts::FRAC_PI_2, 1e-6);
+        assert_approx(twist_from_degrees(359), 359_f32.to_radians(), 1e-6);
+        assert_eq!(twist_from_degrees(360), 0.0);
+    }
 }
@@ -0,0 +1,123 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support routines for converting composition and input events from [`web_sys`].
+//!
+//! To do IME text entry in an element that is not a native text field, such as a
+//! canvas, listen for `compositionstart`, `compositionupdate`, and `compositionend`
+//! and convert them with [`ime_events_from_composition_event`], and listen for
+//! `beforeinput` and convert it with [`from_web_input_event`].
+//! The element must be focusable and editable (for example through `contenteditable`,
+//! or a hidden `<textarea>` that receives focus) for the browser to send these events.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use ui_events::text_input::{ImeEvent, InputType, TextInputEvent};
+use web_sys::{CompositionEvent, InputEvent};
+
+/// Convert a DOM `compositionstart`, `compositionupdate`, or `compositionend` event
+/// to [`ImeEvent`]s.
+///
+/// The DOM has no notion of enabling an IME, so each composition is reported as
+/// the IME being enabled and then disabled, following the sequence described in
+/// [`ui_events::text_input`]:
+///
+/// - `compositionstart` becomes [`ImeEvent::Enabled`]. Its `data` is the text the
+///   composition will replace, not preedit text, so it is not reported.
+/// - `compositionupdate` becomes [`ImeEvent::Preedit`], with the cursor at the end
+///   of the preedit text, since the DOM does not report it.
+/// - `compositionend` becomes an empty [`ImeEvent::Preedit`], then
+///   [`ImeEvent::Commit`], then [`ImeEvent::Disabled`]. A cancelled composition
+///   ends with empty `data`, and gives no `Commit`.
+///
+/// Other event types give no events.
+pub fn ime_events_from_composition_event(e: &CompositionEvent) -> Vec<ImeEvent> {
+    ime_events_from_composition(&e.type_(), e.data())
+}
+
+fn ime_events_from_composition(type_: &str, data: Option<String>) -> Vec<ImeEvent> {
+    let data = data.unwrap_or_default();
+    match type_ {
+        "compositionstart" => vec![ImeEvent::Enabled],
+        "compositionupdate" => {
+            let end = data.len();
+            vec![ImeEvent::Preedit {
+                text: data,
+                cursor: Some(end..end),
+            }]
+        }
+        "compositionend" => {
+            let mut events = vec![ImeEvent::Preedit {
+                text: String::new(),
+                cursor: None,
+            }];
+            if !data.is_empty() {
+                events.push(ImeEvent::Commit(data));
+            }
+            events.push(ImeEvent::Disabled);
+            events
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a DOM `beforeinput` or `input` event to a [`TextInputEvent`].
+///
+/// Handle `beforeinput` and call `preventDefault` on it if you apply the edit yourself;
+/// `input` fires after the browser has already changed the element's content.
+pub fn from_web_input_event(e: &InputEvent) -> TextInputEvent {
+    TextInputEvent {
+        input_type: InputType::from_name(&e.input_type()),
+        data: e.data(),
+        is_composing: e.is_composing(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composition_mapping() {
+        // The data of `compositionstart` is the selection being replaced.
+        assert_eq!(
+            ime_events_from_composition("compositionstart", Some("foo".into())),
+            [ImeEvent::Enabled]
+        );
+        assert_eq!(
+            ime_events_from_composition("compositionupdate", Some("ni".into())),
+            [ImeEvent::Preedit {
+                text: "ni".into(),
+                cursor: Some(2..2),
+            }]
+        );
+        let end_preedit = ImeEvent::Preedit {
+            text: String::new(),
+            cursor: None,
+        };
+        assert_eq!(
+            ime_events_from_composition("compositionend", Some("你".into())),
+            [
+                end_preedit.clone(),
+                ImeEvent::Commit("你".into()),
+                ImeEvent::Disabled
+            ]
+        );
+        // Cancelled.
+        assert_eq!(
+            ime_events_from_composition("compositionend", None),
+            [end_preedit, ImeEvent::Disabled]
+        );
+        assert_eq!(ime_events_from_composition("input", None), []);
+    }
+
+    #[test]
+    fn composition_ends_not_composing() {
+        let events = ["compositionstart", "compositionupdate", "compositionend"]
+            .into_iter()
+            .flat_map(|t| ime_events_from_composition(t, Some("a".into())));
+        let composing: Vec<_> = events.map(|e| e.is_composing()).collect();
+        assert_eq!(composing, [false, true, false, false, false]);
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Sources of event times.
+//!
+//! Winit does not timestamp its events, so a [`WindowEventReducer`] reads
+//! [`PointerState::time`] from a [`Clock`] when each event is reduced.
+//! [`InstantClock`] is used unless another is set with
+//! [`WindowEventReducer::set_clock`]. To give times explicitly, for example in
+//! tests or when replaying recorded input, use [`WindowEventReducer::reduce_at`].
+//!
+//! [`WindowEventReducer`]: crate::WindowEventReducer
+//! [`WindowEventReducer::set_clock`]: crate::WindowEventReducer::set_clock
+//! [`WindowEventReducer::reduce_at`]: crate::WindowEventReducer::reduce_at
+//! [`PointerState::time`]: ui_events::pointer::PointerState::time
+
+extern crate alloc;
+use alloc::boxed::Box;
+
+use crate::Instant;
+
+/// A source of event times.
+pub trait Clock: core::fmt::Debug {
+    /// The current time, in nanoseconds.
+    ///
+    /// This must never decrease.
+    fn now(&mut self) -> u64;
+}
+
+/// A [`Clock`] counting nanoseconds since it was first read, using [`Instant`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InstantClock {
+    /// First time the clock was read.
+    first_instant: Option<Instant>,
+}
+
+impl Clock for InstantClock {
+    #[allow(
+        clippy::cast_possible_truncation,
+        reason = "A u64 of nanoseconds lasts for centuries."
+    )]
+    fn now(&mut self) -> u64 {
+        Instant::now()
+            .duration_since(*self.first_instant.get_or_insert_with(Instant::now))
+            .as_nanos() as u64
+    }
+}
+
+/// The [`Clock`] of a reducer or router, which is an [`InstantClock`] unless another is set.
+#[derive(Debug, Default)]
+pub(crate) struct ClockSource {
+    /// Source of event times, if not the default.
+    clock: Option<Box<dyn Clock>>,
+    /// Source of event times by default.
+    default_clock: InstantClock,
+}
+
+impl ClockSource {
+    /// Replace the clock.
+    pub(crate) fn set(&mut self, clock: impl Clock + 'static) {
+        self.clock = Some(Box::new(clock));
+    }
+
+    /// Read the time from the clock.
+    pub(crate) fn now(&mut self) -> u64 {
+        match &mut self.clock {
+            Some(clock) => clock.now(),
+            None => self.default_clock.now(),
+        }
+    }
+}
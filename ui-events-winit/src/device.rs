@@ -0,0 +1,107 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Tracking of the input devices seen by a [`WindowEventReducer`].
+//!
+//! Winit identifies devices with opaque [`DeviceId`]s. The reducer interns each one
+//! it sees into a [`PersistentDeviceId`], numbered from 1 in order of appearance,
+//! and reports it in [`PointerInfo::persistent_device_id`].
+//!
+//! How finely devices are told apart depends on the platform: some report every
+//! mouse and tablet separately, while others, like Wayland, give all of them the
+//! same [`DeviceId`].
+//!
+//! Raw [`DeviceEvent`]s, which are not tied to a window, are translated by
+//! [`WindowEventReducer::reduce_device_event`] into a [`DeviceEventTranslation`].
+//!
+//! [`WindowEventReducer`]: crate::WindowEventReducer
+//! [`WindowEventReducer::reduce_device_event`]: crate::WindowEventReducer::reduce_device_event
+//! [`DeviceEventTranslation`]: crate::DeviceEventTranslation
+//! [`DeviceEvent`]: winit::event::DeviceEvent
+//! [`PointerInfo::persistent_device_id`]: ui_events::pointer::PointerInfo::persistent_device_id
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use ui_events::pointer::{PersistentDeviceId, PointerInfo, PointerState};
+use winit::event::{ButtonId, DeviceId};
+
+/// An input device seen by a [`WindowEventReducer`].
+///
+/// Times are in nanoseconds, in the time base of [`PointerState::time`].
+///
+/// [`WindowEventReducer`]: crate::WindowEventReducer
+/// [`PointerState::time`]: ui_events::pointer::PointerState::time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Stable identifier of the device for this reducer.
+    pub persistent_device_id: PersistentDeviceId,
+    /// Winit's identifier of the device.
+    pub device_id: DeviceId,
+    /// Time of the first event from the device.
+    pub first_seen: u64,
+    /// Time of the latest event from the device.
+    pub last_seen: u64,
+}
+
+/// A raw press or release of a button on a device.
+///
+/// Winit does not say how a [`ButtonId`] relates to a [`MouseButton`], and the
+/// numbering differs between platforms, so it is passed through as is.
+///
+/// [`MouseButton`]: winit::event::MouseButton
+#[derive(Clone, Debug)]
+pub struct RawButtonEvent {
+    /// Identity of the pointer the device drives.
+    pub pointer: PointerInfo,
+    /// Platform-specific identifier of the button.
+    pub button: ButtonId,
+    /// Whether the button was pressed, rather than released.
+    pub pressed: bool,
+    /// The state of the pointer (i.e. position, pressure, etc.).
+    pub state: PointerState,
+}
+
+/// Interning table from [`DeviceId`] to [`PersistentDeviceId`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DeviceTable {
+    /// Devices in order of appearance, so the index is the persistent ID minus 1.
+    devices: Vec<DeviceInfo>,
+}
+
+impl DeviceTable {
+    /// Record an event from `device_id` at `time`, returning its persistent ID.
+    pub(crate) fn intern(&mut self, device_id: DeviceId, time: u64) -> PersistentDeviceId {
+        if let Some(device) = self.devices.iter_mut().find(|d| d.device_id == device_id) {
+            device.last_seen = device.last_seen.max(time);
+            return device.persistent_device_id;
+        }
+        let persistent_device_id = PersistentDeviceId::new(self.devices.len() as u64 + 1)
+            .expect("one more than a length is never zero");
+        self.devices.push(DeviceInfo {
+            persistent_device_id,
+            device_id,
+            first_seen: time,
+            last_seen: time,
+        });
+        persistent_device_id
+    }
+
+    /// The persistent ID of `device_id`, if it has been seen.
+    pub(crate) fn persistent_device_id(&self, device_id: DeviceId) -> Option<PersistentDeviceId> {
+        self.devices
+            .iter()
+            .find(|d| d.device_id == device_id)
+            .map(|d| d.persistent_device_id)
+    }
+
+    /// The device with the persistent ID `id`, if any.
+    pub(crate) fn get(&self, id: PersistentDeviceId) -> Option<&DeviceInfo> {
+        self.devices.iter().find(|d| d.persistent_device_id == id)
+    }
+
+    /// All devices seen, in order of appearance.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &DeviceInfo> {
+        self.devices.iter()
+    }
+}
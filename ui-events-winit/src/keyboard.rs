@@ -10,6 +10,11 @@ extern crate alloc;
 use alloc::string::String;
 
 /// Convert a [`winit::event::KeyEvent`] and [`winit::keyboard::ModifiersState`] to a [`KeyboardEvent`].
+///
+/// Winit does not report whether an IME composition is in progress, so `is_composing`
+/// is always `false`. [`WindowEventReducer`] tracks composition and sets it correctly.
+///
+/// [`WindowEventReducer`]: crate::WindowEventReducer
 pub fn from_winit_keyboard_event(
     winit_event: winit::event::KeyEvent,
     mods: ModifiersState,
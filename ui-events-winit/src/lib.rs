@@ -4,7 +4,8 @@
 //! This crate bridges [`winit`]'s native input events (mouse, touch, keyboard, etc.)
 //! into the [`ui-events`] model.
 //!
-//! The primary entry point is [`WindowEventReducer`].
+//! The primary entry point is [`WindowEventReducer`], or [`WindowEventRouter`]
+//! for applications with several windows.
 //!
 //! [`ui-events`]: https://docs.rs/ui-events/
 
@@ -19,8 +20,13 @@
 // END LINEBENDER LINT SET
 #![no_std]
 
+pub mod clock;
+pub mod device;
 pub mod keyboard;
+pub mod pen;
 pub mod pointer;
+pub mod router;
+pub mod text_input;
 
 extern crate alloc;
 use alloc::{vec, vec::Vec};
@@ -34,16 +40,23 @@ pub use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 pub use web_time::Instant;
 
+use clock::ClockSource;
+pub use clock::{Clock, InstantClock};
+use device::{DeviceInfo, DeviceTable, RawButtonEvent};
+pub use router::WindowEventRouter;
 use ui_events::{
-    ScrollDelta,
+    ScrollPhase,
     keyboard::KeyboardEvent,
     pointer::{
-        PointerButtonEvent, PointerEvent, PointerGesture, PointerGestureEvent, PointerId,
-        PointerInfo, PointerScrollEvent, PointerState, PointerType, PointerUpdate,
+        ClickCountConfig, ClickCounter, MotionDelta, MotionPredictor, PersistentDeviceId,
+        PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerGesture,
+        PointerGestureEvent, PointerId, PointerInfo, PointerScrollEvent, PointerState, PointerType,
+        PointerUpdate, PredictionConfig, RelativeMotionEvent,
     },
+    text_input::ImeEvent,
 };
 use winit::{
-    event::{ElementState, Force, MouseScrollDelta, Touch, TouchPhase, WindowEvent},
+    event::{DeviceEvent, DeviceId, ElementState, Force, Touch, TouchPhase, WindowEvent},
     keyboard::ModifiersState,
 };
 
@@ -51,11 +64,14 @@ use winit::{
 ///
 /// Store a single instance of this per window, then call [`WindowEventReducer::reduce`]
 /// on each [`WindowEvent`] for that window.
-/// Use the [`WindowEventTranslation`] value to receive [`PointerEvent`]s and [`KeyboardEvent`]s.
+/// With several windows, a [`WindowEventRouter`] can do this for you.
+/// Use the [`WindowEventTranslation`] value to receive [`PointerEvent`]s, [`KeyboardEvent`]s,
+/// and [`ImeEvent`]s.
 ///
 /// This handles:
 ///  - [`ModifiersChanged`][`WindowEvent::ModifiersChanged`]
 ///  - [`KeyboardInput`][`WindowEvent::KeyboardInput`]
+///  - [`Ime`][`WindowEvent::Ime`]
 ///  - [`Touch`][`WindowEvent::Touch`]
 ///  - [`MouseInput`][`WindowEvent::MouseInput`]
 ///  - [`MouseWheel`][`WindowEvent::MouseWheel`]
@@ -64,16 +80,47 @@ use winit::{
 ///  - [`CursorLeft`][`WindowEvent::CursorLeft`]
 ///  - [`PinchGesture`][`WindowEvent::PinchGesture`]
 ///  - [`RotationGesture`][`WindowEvent::RotationGesture`]
+///
+/// Every [`CursorMoved`][`WindowEvent::CursorMoved`] and touch motion becomes its own
+/// [`PointerEvent::Move`]. To deliver one move per pointer per frame, with the rest
+/// in [`PointerUpdate::coalesced`] as browsers do, pass pointer events through a
+/// [`MoveCoalescer`], and flush it before keyboard and IME events and at the end of
+/// each batch of events, e.g. in `about_to_wait`.
+///
+/// Touch contacts get their own [`PointerId`]s, and are reported as [`PointerType::Pen`]
+/// when winit can tell that they come from a stylus; see the [`pen`] module. Following the W3C rules for primary
+/// pointers, a contact made while no other is active becomes [`PointerId::PRIMARY`],
+/// until it lifts; contacts made while any other is active are never primary.
+/// Each contact is bracketed by an [`Enter`][`PointerEvent::Enter`] and a
+/// [`Leave`][`PointerEvent::Leave`], as on the web.
+///
+/// Each winit [`DeviceId`] is given a [`PersistentDeviceId`], reported in
+/// [`PointerInfo::persistent_device_id`]; see the [`device`] module.
+///
+/// For raw mouse motion, which keeps coming when the cursor cannot move, such as
+/// while it is grabbed, pass [`DeviceEvent`]s to [`WindowEventReducer::reduce_device_event`].
+///
+/// [`MoveCoalescer`]: ui_events::pointer::MoveCoalescer
 #[derive(Debug, Default)]
 pub struct WindowEventReducer {
     /// State of modifiers.
     modifiers: ModifiersState,
+    /// Whether an IME composition is in progress.
+    composing: bool,
     /// State of the primary mouse pointer.
     primary_state: PointerState,
     /// Click and tap counter.
-    counter: TapCounter,
-    /// First time an event was received..
-    first_instant: Option<Instant>,
+    counter: ClickCounter,
+    /// Predictor for [`PointerUpdate::predicted`], if enabled.
+    predictor: Option<MotionPredictor>,
+    /// Devices seen so far.
+    devices: DeviceTable,
+    /// Winit ids and types of the active touch contacts.
+    touches: Vec<(u64, PointerType)>,
+    /// Winit id of the primary touch contact, if it is active.
+    primary_touch: Option<u64>,
+    /// Source of event times.
+    clock: ClockSource,
 }
 
 #[allow(
@@ -81,48 +128,138 @@ pub struct WindowEventReducer {
     reason = "There is no alternative to truncation here."
 )]
 impl WindowEventReducer {
-    /// Process a [`WindowEvent`].
-    pub fn reduce(
+    /// Set how clicks and taps are counted into [`PointerState::count`].
+    pub fn set_click_count_config(&mut self, config: ClickCountConfig) {
+        self.counter = ClickCounter::new(config);
+    }
+
+    /// Enable or disable filling [`PointerUpdate::predicted`] with a [`MotionPredictor`].
+    ///
+    /// Winit does not report predicted states, so this is disabled by default.
+    /// Prediction can hide some of the latency between input and display,
+    /// which is most noticeable when drawing with a pen.
+    pub fn set_motion_prediction(&mut self, config: Option<PredictionConfig>) {
+        self.predictor = config.map(MotionPredictor::new);
+    }
+
+    /// Set the [`Clock`] that [`WindowEventReducer::reduce`] reads event times from.
+    ///
+    /// By default, this is an [`InstantClock`].
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock.set(clock);
+    }
+
+    /// The [`PersistentDeviceId`] given to a winit [`DeviceId`], if it has been seen.
+    pub fn persistent_device_id(&self, device_id: DeviceId) -> Option<PersistentDeviceId> {
+        self.devices.persistent_device_id(device_id)
+    }
+
+    /// The device with the given [`PersistentDeviceId`], if any.
+    pub fn device(&self, id: PersistentDeviceId) -> Option<&DeviceInfo> {
+        self.devices.get(id)
+    }
+
+    /// All devices seen so far, in order of appearance.
+    pub fn devices(&self) -> impl Iterator<Item = &DeviceInfo> {
+        self.devices.iter()
+    }
+
+    /// Process a [`WindowEvent`], returning the resulting translations in order.
+    ///
+    /// Most events give at most one translation. A touch or pen contact also gives a
+    /// [`PointerEvent::Enter`] before it goes down, and a [`PointerEvent::Leave`] after it
+    /// lifts or is cancelled, so that touch has the same lifecycle as on the web.
+    ///
+    /// The time of the event is read from the [`Clock`].
+    pub fn reduce(&mut self, scale_factor: f64, we: &WindowEvent) -> Translations {
+        let time = self.clock.now();
+        self.reduce_at(time, scale_factor, we)
+    }
+
+    /// Process a [`WindowEvent`] that happened at `time`.
+    ///
+    /// `time` is in nanoseconds, and becomes [`PointerState::time`]. It should not
+    /// decrease from one event to the next, and does not affect the [`Clock`].
+    ///
+    /// See [`WindowEventReducer::reduce`].
+    pub fn reduce_at(&mut self, time: u64, scale_factor: f64, we: &WindowEvent) -> Translations {
+        let Some(translation) = self.translate(time, scale_factor, we) else {
+            return Translations::default();
+        };
+        match translation {
+            WindowEventTranslation::Pointer(PointerEvent::Down(ref e)) if is_contact(e.pointer) => {
+                let enter = WindowEventTranslation::Pointer(PointerEvent::Enter(e.pointer));
+                Translations::two(enter, translation)
+            }
+            WindowEventTranslation::Pointer(
+                PointerEvent::Up(PointerButtonEvent { pointer, .. })
+                | PointerEvent::Cancel(pointer),
+            ) if is_contact(pointer) => {
+                let leave = WindowEventTranslation::Pointer(PointerEvent::Leave(pointer));
+                Translations::two(translation, leave)
+            }
+            translation => Translations::one(translation),
+        }
+    }
+
+    /// Translate a [`WindowEvent`] into its main translation, if any.
+    fn translate(
         &mut self,
+        time: u64,
         scale_factor: f64,
         we: &WindowEvent,
     ) -> Option<WindowEventTranslation> {
-        const PRIMARY_MOUSE: PointerInfo = PointerInfo {
+        self.primary_state.time = time;
+        self.primary_state.scale_factor = scale_factor;
+
+        let persistent_device_id = match we {
+            WindowEvent::KeyboardInput { device_id, .. }
+            | WindowEvent::CursorMoved { device_id, .. }
+            | WindowEvent::CursorEntered { device_id }
+            | WindowEvent::CursorLeft { device_id }
+            | WindowEvent::MouseWheel { device_id, .. }
+            | WindowEvent::MouseInput { device_id, .. }
+            | WindowEvent::PinchGesture { device_id, .. }
+            | WindowEvent::RotationGesture { device_id, .. }
+            | WindowEvent::Touch(Touch { device_id, .. }) => {
+                Some(self.devices.intern(*device_id, time))
+            }
+            _ => None,
+        };
+        let primary_mouse = PointerInfo {
             pointer_id: Some(PointerId::PRIMARY),
-            // TODO: Maybe transmute device.
-            persistent_device_id: None,
+            persistent_device_id,
             pointer_type: PointerType::Mouse,
         };
 
-        let time = Instant::now()
-            .duration_since(*self.first_instant.get_or_insert_with(Instant::now))
-            .as_nanos() as u64;
-
-        self.primary_state.time = time;
-        self.primary_state.scale_factor = scale_factor;
-
-        match we {
+        let translation = match we {
             WindowEvent::ModifiersChanged(m) => {
                 self.modifiers = m.state();
                 self.primary_state.modifiers = keyboard::from_winit_modifier_state(self.modifiers);
                 None
             }
-            WindowEvent::KeyboardInput { event, .. } => Some(WindowEventTranslation::Keyboard(
-                keyboard::from_winit_keyboard_event(event.clone(), self.modifiers),
-            )),
+            WindowEvent::KeyboardInput { event, .. } => {
+                let mut event = keyboard::from_winit_keyboard_event(event.clone(), self.modifiers);
+                event.is_composing = self.composing;
+                Some(WindowEventTranslation::Keyboard(event))
+            }
+            WindowEvent::Ime(ime) => {
+                let ime = text_input::from_winit_ime(ime.clone());
+                self.composing = ime.is_composing();
+                Some(WindowEventTranslation::Ime(ime))
+            }
             WindowEvent::CursorEntered { .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Enter(PRIMARY_MOUSE),
+                PointerEvent::Enter(primary_mouse),
             )),
             WindowEvent::CursorLeft { .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Leave(PRIMARY_MOUSE),
+                PointerEvent::Leave(primary_mouse),
             )),
             WindowEvent::CursorMoved { position, .. } => {
                 self.primary_state.position = *position;
 
                 Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    scale_factor,
                     PointerEvent::Move(PointerUpdate {
-                        pointer: PRIMARY_MOUSE,
+                        pointer: primary_mouse,
                         current: self.primary_state.clone(),
                         coalesced: vec![],
                         predicted: vec![],
@@ -140,9 +277,8 @@ impl WindowEventReducer {
                 }
 
                 Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    scale_factor,
                     PointerEvent::Down(PointerButtonEvent {
-                        pointer: PRIMARY_MOUSE,
+                        pointer: primary_mouse,
                         button,
                         state: self.primary_state.clone(),
                     }),
@@ -159,28 +295,34 @@ impl WindowEventReducer {
                 }
 
                 Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    scale_factor,
                     PointerEvent::Up(PointerButtonEvent {
-                        pointer: PRIMARY_MOUSE,
+                        pointer: primary_mouse,
                         button,
                         state: self.primary_state.clone(),
                     }),
                 )))
             }
-            WindowEvent::MouseWheel { delta, .. } => Some(WindowEventTranslation::Pointer(
-                PointerEvent::Scroll(PointerScrollEvent {
-                    pointer: PRIMARY_MOUSE,
-                    delta: match *delta {
-                        MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
-                        MouseScrollDelta::PixelDelta(p) => ScrollDelta::PixelDelta(p),
+            WindowEvent::MouseWheel { delta, phase, .. } => {
+                let (delta, source) = pointer::from_winit_scroll_delta(*delta);
+                Some(WindowEventTranslation::Pointer(PointerEvent::Scroll(
+                    PointerScrollEvent {
+                        pointer: primary_mouse,
+                        delta,
+                        phase: match phase {
+                            TouchPhase::Started => ScrollPhase::Started,
+                            TouchPhase::Moved => ScrollPhase::Changed,
+                            TouchPhase::Ended => ScrollPhase::Ended,
+                            TouchPhase::Cancelled => ScrollPhase::Cancelled,
+                        },
+                        source,
+                        state: self.primary_state.clone(),
                     },
-                    state: self.primary_state.clone(),
-                }),
-            )),
+                )))
+            }
             // Winit documentation says delta can be NaN; that is totally useless, so discard.
             WindowEvent::PinchGesture { delta, .. } if delta.is_finite() => Some(
                 WindowEventTranslation::Pointer(PointerEvent::Gesture(PointerGestureEvent {
-                    pointer: PRIMARY_MOUSE,
+                    pointer: primary_mouse,
                     gesture: PointerGesture::Pinch(*delta as f32),
                     state: self.primary_state.clone(),
                 })),
@@ -189,7 +331,7 @@ impl WindowEventReducer {
             WindowEvent::RotationGesture { delta, .. } if delta.is_finite() => {
                 Some(WindowEventTranslation::Pointer(PointerEvent::Gesture(
                     PointerGestureEvent {
-                        pointer: PRIMARY_MOUSE,
+                        pointer: primary_mouse,
                         // Winit gives this in counterclockwise degrees.
                         gesture: PointerGesture::Rotate((-*delta).to_radians()),
                         state: self.primary_state.clone(),
@@ -203,36 +345,69 @@ impl WindowEventReducer {
                 force,
                 ..
             }) => {
+                use TouchPhase::*;
+
+                // Keep the type a contact started with, in case its force changes.
+                let pointer_type = match self.touches.iter().find(|(t, _)| t == id) {
+                    Some(&(_, pointer_type)) => pointer_type,
+                    None => pen::pointer_type_from_force(*force),
+                };
+                if *phase == Started && !self.touches.iter().any(|(t, _)| t == id) {
+                    if self.touches.is_empty() {
+                        self.primary_touch = Some(*id);
+                    }
+                    self.touches.push((*id, pointer_type));
+                }
                 let pointer = PointerInfo {
-                    pointer_id: PointerId::new(id.saturating_add(1)),
-                    pointer_type: PointerType::Touch,
-                    persistent_device_id: None,
+                    pointer_id: if self.primary_touch == Some(*id) {
+                        Some(PointerId::PRIMARY)
+                    } else {
+                        // Offset to avoid colliding with the primary pointer.
+                        PointerId::new(id.saturating_add(2))
+                    },
+                    pointer_type,
+                    persistent_device_id,
                 };
-
-                use TouchPhase::*;
+                let lifted = matches!(phase, Ended | Cancelled);
+                if lifted {
+                    self.touches.retain(|(t, _)| t != id);
+                    if self.primary_touch == Some(*id) {
+                        self.primary_touch = None;
+                    }
+                }
 
                 let state = PointerState {
                     time,
                     position: *location,
                     modifiers: self.primary_state.modifiers,
-                    pressure: if matches!(phase, Ended | Cancelled) {
-                        0.0
+                    scale_factor,
+                    buttons: if pointer_type == PointerType::Pen && !lifted {
+                        PointerButton::Primary.into()
                     } else {
-                        match force {
-                            Some(Force::Calibrated { force, .. }) => (force * 0.5) as f32,
-                            Some(Force::Normalized(q)) => *q as f32,
-                            _ => 0.5,
+                        PointerButtons::default()
+                    },
+                    orientation: force
+                        .and_then(pen::orientation_from_force)
+                        .unwrap_or_default(),
+                    pressure: match force {
+                        _ if lifted => 0.0,
+                        Some(force) if pointer_type == PointerType::Pen => {
+                            pen::pen_pressure_from_force(*force)
                         }
+                        Some(Force::Calibrated { force, .. }) => (force * 0.5) as f32,
+                        Some(Force::Normalized(q)) => *q as f32,
+                        _ => 0.5,
                     },
                     ..Default::default()
                 };
+                // Pen contacts press the primary button, as on the web.
+                let button = (pointer_type == PointerType::Pen).then_some(PointerButton::Primary);
 
                 Some(WindowEventTranslation::Pointer(self.counter.attach_count(
-                    scale_factor,
                     match phase {
                         Started => PointerEvent::Down(PointerButtonEvent {
                             pointer,
-                            button: None,
+                            button,
                             state,
                         }),
                         Moved => PointerEvent::Move(PointerUpdate {
@@ -244,17 +419,99 @@ impl WindowEventReducer {
                         Cancelled => PointerEvent::Cancel(pointer),
                         Ended => PointerEvent::Up(PointerButtonEvent {
                             pointer,
-                            button: None,
+                            button,
                             state,
                         }),
                     },
                 )))
             }
             _ => None,
+        };
+
+        match (translation, &mut self.predictor) {
+            (Some(WindowEventTranslation::Pointer(e)), Some(predictor)) => Some(
+                WindowEventTranslation::Pointer(predictor.attach_predictions(e)),
+            ),
+            (translation, _) => translation,
+        }
+    }
+
+    /// Process a [`DeviceEvent`] from the device `device_id`.
+    ///
+    /// This handles:
+    ///  - [`MouseMotion`][`DeviceEvent::MouseMotion`], as a [`MotionDelta::RawDelta`]
+    ///  - [`MouseWheel`][`DeviceEvent::MouseWheel`]
+    ///  - [`Button`][`DeviceEvent::Button`]
+    ///
+    /// These are reported by the device whether or not the cursor is in the window,
+    /// or can move, and are not passed to window-specific state such as click counts
+    /// and motion prediction. They are reported for the primary mouse pointer, and
+    /// carry its last [`PointerState`].
+    ///
+    /// The time of the event is read from the [`Clock`].
+    pub fn reduce_device_event(
+        &mut self,
+        device_id: DeviceId,
+        de: &DeviceEvent,
+    ) -> Option<DeviceEventTranslation> {
+        let time = self.clock.now();
+        self.reduce_device_event_at(time, device_id, de)
+    }
+
+    /// Process a [`DeviceEvent`] from the device `device_id` that happened at `time`.
+    ///
+    /// See [`WindowEventReducer::reduce_device_event`] and [`WindowEventReducer::reduce_at`].
+    pub fn reduce_device_event_at(
+        &mut self,
+        time: u64,
+        device_id: DeviceId,
+        de: &DeviceEvent,
+    ) -> Option<DeviceEventTranslation> {
+        self.primary_state.time = time;
+        let pointer = PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: Some(self.devices.intern(device_id, time)),
+            pointer_type: PointerType::Mouse,
+        };
+        let state = self.primary_state.clone();
+
+        match de {
+            DeviceEvent::MouseMotion { delta: (x, y) } => {
+                Some(DeviceEventTranslation::Motion(RelativeMotionEvent {
+                    pointer,
+                    delta: MotionDelta::RawDelta(*x, *y),
+                    state,
+                }))
+            }
+            DeviceEvent::MouseWheel { delta } => {
+                let (delta, source) = pointer::from_winit_scroll_delta(*delta);
+                Some(DeviceEventTranslation::Scroll(PointerScrollEvent {
+                    pointer,
+                    delta,
+                    // Device events have no phases.
+                    phase: ScrollPhase::Changed,
+                    source,
+                    state,
+                }))
+            }
+            DeviceEvent::Button { button, state: s } => {
+                Some(DeviceEventTranslation::Button(RawButtonEvent {
+                    pointer,
+                    button: *button,
+                    pressed: *s == ElementState::Pressed,
+                    state,
+                }))
+            }
+            _ => None,
         }
     }
 }
 
+/// Whether a pointer is a touch or pen contact, which enters and leaves as it goes down and up.
+fn is_contact(pointer: PointerInfo) -> bool {
+    matches!(pointer.pointer_type, PointerType::Touch | PointerType::Pen)
+}
+
 /// Result of [`WindowEventReducer::reduce`].
 #[derive(Debug)]
 pub enum WindowEventTranslation {
@@ -262,174 +519,479 @@ pub enum WindowEventTranslation {
     Keyboard(KeyboardEvent),
     /// Resulting [`PointerEvent`].
     Pointer(PointerEvent),
+    /// Resulting [`ImeEvent`].
+    Ime(ImeEvent),
 }
 
-#[derive(Clone, Debug)]
-struct TapState {
-    /// Pointer ID used to attach tap counts to [`PointerEvent::Move`].
-    pointer_id: Option<PointerId>,
-    /// Nanosecond timestamp when the tap went Down.
-    down_time: u64,
-    /// Nanosecond timestamp when the tap went Up.
-    ///
-    /// Resets to `down_time` when tap goes Down.
-    up_time: u64,
-    /// The local tap count as of the last Down phase.
-    count: u8,
-    /// x coordinate.
-    x: f64,
-    /// y coordinate.
-    y: f64,
+/// The translations of one [`WindowEvent`], in order.
+///
+/// Returned by [`WindowEventReducer::reduce`]. This holds at most two
+/// translations, without allocating.
+#[derive(Debug, Default)]
+pub struct Translations {
+    /// The next translation.
+    first: Option<WindowEventTranslation>,
+    /// The translation after `first`.
+    second: Option<WindowEventTranslation>,
 }
 
-#[derive(Debug, Default)]
-struct TapCounter {
-    taps: Vec<TapState>,
+impl Translations {
+    fn one(first: WindowEventTranslation) -> Self {
+        Self {
+            first: Some(first),
+            second: None,
+        }
+    }
+
+    fn two(first: WindowEventTranslation, second: WindowEventTranslation) -> Self {
+        Self {
+            first: Some(first),
+            second: Some(second),
+        }
+    }
 }
 
-impl TapCounter {
-    /// Enhance a [`PointerEvent`] with a `count`.
-    fn attach_count(&mut self, scale_factor: f64, e: PointerEvent) -> PointerEvent {
-        match e {
-            PointerEvent::Down(mut event) => {
-                let pointer_id = event.pointer.pointer_id;
-                let position = event.state.position;
-                let time = event.state.time;
-
-                let slop = match event.pointer.pointer_type {
-                    // This is on the low side of double tap slop, validated
-                    // experimentally to work on a few touchscreen laptops.
-                    PointerType::Touch => 12.0,
-                    PointerType::Pen => 6.0,
-                    // This is slightly more forgiving than the default on Windows for mice.
-                    // In order to make the slop calculation more similar between devices,
-                    // this uses a slightly different method than Windows, which tests if the
-                    // tap is in a box, rather than in a circle, centered on the anchor point.
-                    _ => 2.0,
-                } * core::f64::consts::SQRT_2
-                    * scale_factor;
-
-                if let Some(tap) =
-                    self.taps.iter_mut().find(|TapState { x, y, up_time, .. }| {
-                        let dx = (x - position.x).abs();
-                        let dy = (y - position.y).abs();
-                        (dx * dx + dy * dy).sqrt() < slop && (up_time + 500_000_000) > time
-                    })
-                {
-                    let count = tap.count + 1;
-                    event.state.count = count;
-                    tap.count = count;
-                    tap.pointer_id = pointer_id;
-                    tap.down_time = time;
-                    tap.up_time = time;
-                    tap.x = position.x;
-                    tap.y = position.y;
-                } else {
-                    let s = TapState {
-                        pointer_id,
-                        down_time: time,
-                        up_time: time,
-                        count: 1,
-                        x: position.x,
-                        y: position.y,
-                    };
-                    if let Some(t) = self
-                        .taps
-                        .iter_mut()
-                        .find(|state| state.pointer_id == pointer_id)
-                    {
-                        *t = s;
-                    } else {
-                        self.taps.push(s);
-                    }
-                    event.state.count = 1;
-                };
-                self.clear_expired(time);
-                PointerEvent::Down(event)
-            }
-            PointerEvent::Up(mut event) => {
-                let p_id = event.pointer.pointer_id;
-                if let Some(tap) = self.taps.iter_mut().find(|state| state.pointer_id == p_id) {
-                    tap.up_time = event.state.time;
-                    event.state.count = tap.count;
-                }
-                PointerEvent::Up(event)
+impl Iterator for Translations {
+    type Item = WindowEventTranslation;
+
+    fn next(&mut self) -> Option<WindowEventTranslation> {
+        self.first.take().or_else(|| self.second.take())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.first.is_some()) + usize::from(self.second.is_some());
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Translations {}
+
+impl core::iter::FusedIterator for Translations {}
+
+/// Result of [`WindowEventReducer::reduce_device_event`].
+#[derive(Debug)]
+pub enum DeviceEventTranslation {
+    /// Raw relative motion of a mouse.
+    Motion(RelativeMotionEvent),
+    /// Scrolling of a mouse wheel or touchpad, wherever the cursor is.
+    Scroll(PointerScrollEvent),
+    /// Raw press or release of a button.
+    Button(RawButtonEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use ui_events::ScrollSource;
+    use winit::event::{Ime, MouseScrollDelta};
+
+    fn reduce_ime(reducer: &mut WindowEventReducer, ime: Ime) -> ImeEvent {
+        match reducer
+            .reduce(1.0, &WindowEvent::Ime(ime))
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [WindowEventTranslation::Ime(e)] => e.clone(),
+            other => panic!("expected an IME event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ime_composition_tracking() {
+        let mut reducer = WindowEventReducer::default();
+        assert_eq!(reduce_ime(&mut reducer, Ime::Enabled), ImeEvent::Enabled);
+        assert!(!reducer.composing);
+
+        assert_eq!(
+            reduce_ime(&mut reducer, Ime::Preedit("a b".into(), Some((1, 3)))),
+            ImeEvent::Preedit {
+                text: "a b".into(),
+                cursor: Some(1..3),
             }
-            PointerEvent::Move(PointerUpdate {
-                pointer,
-                mut current,
-                mut coalesced,
-                mut predicted,
-            }) => {
-                if let Some(TapState { count, .. }) = self
-                    .taps
-                    .iter()
-                    .find(
-                        |TapState {
-                             pointer_id,
-                             down_time,
-                             up_time,
-                             ..
-                         }| {
-                            *pointer_id == pointer.pointer_id && down_time == up_time
-                        },
+        );
+        assert!(reducer.composing);
+
+        // Winit clears the preedit before committing.
+        reduce_ime(&mut reducer, Ime::Preedit(String::new(), None));
+        assert!(!reducer.composing);
+        assert_eq!(
+            reduce_ime(&mut reducer, Ime::Commit("啊不".into())),
+            ImeEvent::Commit("啊不".into())
+        );
+        assert!(!reducer.composing);
+
+        // Disabling the IME abandons any composition.
+        reduce_ime(&mut reducer, Ime::Preedit("x".into(), None));
+        assert!(reducer.composing);
+        assert_eq!(reduce_ime(&mut reducer, Ime::Disabled), ImeEvent::Disabled);
+        assert!(!reducer.composing);
+    }
+
+    fn touch(phase: TouchPhase, id: u64) -> WindowEvent {
+        WindowEvent::Touch(Touch {
+            device_id: winit::event::DeviceId::dummy(),
+            phase,
+            location: winit::dpi::PhysicalPosition::new(0.0, 0.0),
+            force: None,
+            id,
+        })
+    }
+
+    /// Pointer events from a touch, as `(kind, pointer ID)`.
+    fn reduce_touch(
+        reducer: &mut WindowEventReducer,
+        phase: TouchPhase,
+        id: u64,
+    ) -> Vec<(&'static str, u64)> {
+        reducer
+            .reduce(1.0, &touch(phase, id))
+            .map(|t| {
+                let WindowEventTranslation::Pointer(e) = t else {
+                    panic!("expected a pointer event, got {t:?}");
+                };
+                let (kind, info) = match e {
+                    PointerEvent::Down(e) => ("down", e.pointer),
+                    PointerEvent::Up(e) => ("up", e.pointer),
+                    PointerEvent::Move(e) => ("move", e.pointer),
+                    PointerEvent::Cancel(p) => ("cancel", p),
+                    PointerEvent::Enter(p) => ("enter", p),
+                    PointerEvent::Leave(p) => ("leave", p),
+                    e => panic!("unexpected {e:?}"),
+                };
+                assert_eq!(info.pointer_type, PointerType::Touch);
+                (kind, info.pointer_id.unwrap().get_inner().get())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn touch_primary_election_and_lifecycle() {
+        use TouchPhase::*;
+        let mut reducer = WindowEventReducer::default();
+
+        // The first contact is primary, even with a winit id that would collide.
+        assert_eq!(
+            reduce_touch(&mut reducer, Started, 5),
+            [("enter", 1), ("down", 1)]
+        );
+        assert_eq!(
+            reduce_touch(&mut reducer, Started, 0),
+            [("enter", 2), ("down", 2)]
+        );
+        assert_eq!(reduce_touch(&mut reducer, Moved, 5), [("move", 1)]);
+        assert_eq!(
+            reduce_touch(&mut reducer, Ended, 5),
+            [("up", 1), ("leave", 1)]
+        );
+
+        // While another contact is active, new contacts are not primary.
+        assert_eq!(
+            reduce_touch(&mut reducer, Started, 3),
+            [("enter", 5), ("down", 5)]
+        );
+        assert_eq!(
+            reduce_touch(&mut reducer, Cancelled, 0),
+            [("cancel", 2), ("leave", 2)]
+        );
+        assert_eq!(
+            reduce_touch(&mut reducer, Ended, 3),
+            [("up", 5), ("leave", 5)]
+        );
+
+        // Once all have lifted, the next contact is primary again.
+        assert_eq!(
+            reduce_touch(&mut reducer, Started, 3),
+            [("enter", 1), ("down", 1)]
+        );
+        assert_eq!(
+            reduce_touch(&mut reducer, Ended, 3),
+            [("up", 1), ("leave", 1)]
+        );
+    }
+
+    const MS: u64 = 1_000_000;
+
+    /// The one pointer event of some translations, apart from the `Enter` and `Leave`
+    /// around a touch.
+    fn pointer_event(translations: Translations) -> PointerEvent {
+        let mut translations: Vec<_> = translations.collect();
+        if translations.len() > 1 {
+            translations.retain(|t| {
+                !matches!(
+                    t,
+                    WindowEventTranslation::Pointer(
+                        PointerEvent::Enter(..) | PointerEvent::Leave(..)
                     )
-                    .cloned()
-                {
-                    current.count = count;
-                    for event in coalesced.iter_mut() {
-                        event.count = count;
-                    }
-                    for event in predicted.iter_mut() {
-                        event.count = count;
-                    }
-                    PointerEvent::Move(PointerUpdate {
-                        pointer,
-                        current,
-                        coalesced,
-                        predicted,
-                    })
-                } else {
-                    PointerEvent::Move(PointerUpdate {
-                        pointer,
-                        current,
-                        coalesced,
-                        predicted,
-                    })
-                }
-            }
-            PointerEvent::Cancel(p) => {
-                self.taps
-                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
-                PointerEvent::Cancel(p)
+                )
+            });
+        }
+        match <[_; 1]>::try_from(translations) {
+            Ok([WindowEventTranslation::Pointer(e)]) => e,
+            other => panic!("expected a pointer event, got {other:?}"),
+        }
+    }
+
+    fn click_count(e: PointerEvent) -> u8 {
+        match e {
+            PointerEvent::Down(e) | PointerEvent::Up(e) => e.state.count,
+            e => panic!("expected a press or release, got {e:?}"),
+        }
+    }
+
+    #[test]
+    fn click_counting() {
+        let device_id = winit::event::DeviceId::dummy();
+        let mouse = |state| WindowEvent::MouseInput {
+            device_id,
+            state,
+            button: winit::event::MouseButton::Left,
+        };
+        let mut reducer = WindowEventReducer::default();
+        let mut click = |time| {
+            let down = reducer.reduce_at(time, 1.0, &mouse(ElementState::Pressed));
+            let up = reducer.reduce_at(time + 50 * MS, 1.0, &mouse(ElementState::Released));
+            let count = click_count(pointer_event(down));
+            assert_eq!(click_count(pointer_event(up)), count);
+            count
+        };
+
+        assert_eq!(click(0), 1);
+        assert_eq!(click(200 * MS), 2);
+        assert_eq!(click(400 * MS), 3);
+        // Too long after the last release.
+        assert_eq!(click(2_000 * MS), 1);
+    }
+
+    #[test]
+    fn tap_counting() {
+        let mut reducer = WindowEventReducer::default();
+        let mut tap = |time, id| {
+            let down = reducer.reduce_at(time, 1.0, &touch(TouchPhase::Started, id));
+            reducer.reduce_at(time + 50 * MS, 1.0, &touch(TouchPhase::Ended, id));
+            click_count(pointer_event(down))
+        };
+
+        assert_eq!(tap(0, 0), 1);
+        // Winit may give the next contact a different id.
+        assert_eq!(tap(200 * MS, 1), 2);
+        assert_eq!(tap(2_000 * MS, 2), 1);
+    }
+
+    #[test]
+    fn gestures() {
+        let device_id = winit::event::DeviceId::dummy();
+        let mut reducer = WindowEventReducer::default();
+        let gesture = |translation| match pointer_event(translation) {
+            PointerEvent::Gesture(e) => e.gesture,
+            e => panic!("expected a gesture, got {e:?}"),
+        };
+
+        let pinch = |delta| WindowEvent::PinchGesture {
+            device_id,
+            delta,
+            phase: TouchPhase::Moved,
+        };
+        assert!(matches!(
+            gesture(reducer.reduce_at(0, 1.0, &pinch(0.5))),
+            PointerGesture::Pinch(0.5)
+        ));
+        assert_eq!(reducer.reduce_at(0, 1.0, &pinch(f64::NAN)).len(), 0);
+
+        // Counterclockwise degrees become clockwise radians.
+        let rotation = WindowEvent::RotationGesture {
+            device_id,
+            delta: 90.0,
+            phase: TouchPhase::Moved,
+        };
+        assert!(matches!(
+            gesture(reducer.reduce_at(0, 1.0, &rotation)),
+            PointerGesture::Rotate(r) if r == -core::f32::consts::FRAC_PI_2
+        ));
+    }
+
+    #[test]
+    fn scroll_phases() {
+        let mut reducer = WindowEventReducer::default();
+        let mut scroll = |phase| {
+            let wheel = WindowEvent::MouseWheel {
+                device_id: winit::event::DeviceId::dummy(),
+                delta: MouseScrollDelta::PixelDelta(winit::dpi::PhysicalPosition::new(0.0, 1.0)),
+                phase,
+            };
+            match pointer_event(reducer.reduce_at(0, 1.0, &wheel)) {
+                PointerEvent::Scroll(e) => (e.phase, e.source),
+                e => panic!("expected a scroll, got {e:?}"),
             }
-            PointerEvent::Leave(p) => {
-                self.taps
-                    .retain(|TapState { pointer_id, .. }| *pointer_id != p.pointer_id);
-                PointerEvent::Leave(p)
+        };
+
+        assert_eq!(
+            scroll(TouchPhase::Started),
+            (ScrollPhase::Started, ScrollSource::Touchpad)
+        );
+        assert_eq!(scroll(TouchPhase::Moved).0, ScrollPhase::Changed);
+        assert_eq!(scroll(TouchPhase::Ended).0, ScrollPhase::Ended);
+        // A cancelled gesture must not be taken as a normal end, which would fling.
+        assert_eq!(scroll(TouchPhase::Cancelled).0, ScrollPhase::Cancelled);
+    }
+
+    #[test]
+    fn clock_and_prediction() {
+        /// A clock that advances 8ms every time it is read.
+        #[derive(Debug)]
+        struct StepClock(u64);
+        impl Clock for StepClock {
+            fn now(&mut self) -> u64 {
+                self.0 += 8 * MS;
+                self.0
             }
-            e
-            @ (PointerEvent::Enter(..) | PointerEvent::Scroll(..) | PointerEvent::Gesture(..)) => e,
         }
+
+        let mut reducer = WindowEventReducer::default();
+        reducer.set_clock(StepClock(0));
+        reducer.set_motion_prediction(Some(PredictionConfig::default()));
+        let mut last = None;
+        for x in [8.0, 16.0, 24.0] {
+            let moved = WindowEvent::CursorMoved {
+                device_id: winit::event::DeviceId::dummy(),
+                position: winit::dpi::PhysicalPosition::new(x, 0.0),
+            };
+            last = Some(pointer_event(reducer.reduce(1.0, &moved)));
+        }
+        let Some(PointerEvent::Move(update)) = last else {
+            panic!("expected a move");
+        };
+        assert_eq!(update.current.time, 24 * MS);
+        let predicted: Vec<_> = update
+            .predicted
+            .iter()
+            .map(|s| (s.time / MS, s.position.x.round()))
+            .collect();
+        assert_eq!(predicted, [(32, 32.0), (40, 40.0)]);
     }
 
-    /// Clear expired taps.
-    ///
-    /// `t` is the time of the last received event.
-    /// All events have the same time base on Android, so this is valid here.
-    fn clear_expired(&mut self, t: u64) {
-        self.taps.retain(
-            |TapState {
-                 down_time, up_time, ..
-             }| { down_time == up_time || (up_time + 500_000_000) > t },
+    #[test]
+    fn device_events() {
+        let device_id = winit::event::DeviceId::dummy();
+        let mut reducer = WindowEventReducer::default();
+        let moved = WindowEvent::CursorMoved {
+            device_id,
+            position: winit::dpi::PhysicalPosition::new(3.0, 4.0),
+        };
+        reducer.reduce_at(0, 2.0, &moved);
+
+        let motion = DeviceEvent::MouseMotion { delta: (1.5, -2.0) };
+        let Some(DeviceEventTranslation::Motion(e)) =
+            reducer.reduce_device_event_at(8 * MS, device_id, &motion)
+        else {
+            panic!("expected motion");
+        };
+        assert_eq!(e.delta, MotionDelta::RawDelta(1.5, -2.0));
+        assert_eq!(e.pointer.pointer_id, Some(PointerId::PRIMARY));
+        assert_eq!(e.pointer.persistent_device_id, PersistentDeviceId::new(1));
+        // The cursor has not moved.
+        assert_eq!(
+            e.state.position,
+            winit::dpi::PhysicalPosition::new(3.0, 4.0)
+        );
+        assert_eq!(e.state.scale_factor, 2.0);
+        assert_eq!(e.state.time, 8 * MS);
+
+        let wheel = DeviceEvent::MouseWheel {
+            delta: MouseScrollDelta::LineDelta(0.0, 1.0),
+        };
+        assert!(matches!(
+            reducer.reduce_device_event_at(9 * MS, device_id, &wheel),
+            Some(DeviceEventTranslation::Scroll(PointerScrollEvent {
+                source: ScrollSource::Wheel,
+                phase: ScrollPhase::Changed,
+                ..
+            }))
+        ));
+
+        let button = DeviceEvent::Button {
+            button: 3,
+            state: ElementState::Pressed,
+        };
+        assert!(matches!(
+            reducer.reduce_device_event_at(10 * MS, device_id, &button),
+            Some(DeviceEventTranslation::Button(RawButtonEvent {
+                button: 3,
+                pressed: true,
+                ..
+            }))
+        ));
+        assert!(
+            reducer
+                .reduce_device_event_at(10 * MS, device_id, &DeviceEvent::Added)
+                .is_none()
         );
+        // Device events do not hold buttons on the pointer.
+        assert!(reducer.primary_state.buttons.is_empty());
+    }
+
+    #[test]
+    fn devices_are_interned() {
+        let mut reducer = WindowEventReducer::default();
+        let device_id = winit::event::DeviceId::dummy();
+        assert_eq!(reducer.persistent_device_id(device_id), None);
+
+        let PointerEvent::Enter(info) =
+            pointer_event(reducer.reduce(1.0, &WindowEvent::CursorEntered { device_id }))
+        else {
+            panic!("expected an enter");
+        };
+        let id = PersistentDeviceId::new(1);
+        assert_eq!(info.persistent_device_id, id);
+        assert_eq!(reducer.persistent_device_id(device_id), id);
+        let first = *reducer.device(id.unwrap()).unwrap();
+
+        reducer.reduce(1.0, &touch(TouchPhase::Started, 0));
+        assert_eq!(reducer.devices().count(), 1);
+        let device = reducer.device(id.unwrap()).unwrap();
+        assert_eq!(device.device_id, device_id);
+        assert_eq!(device.first_seen, first.first_seen);
+        assert!(device.last_seen >= first.last_seen);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    // CI will fail unless cargo nextest can execute at least one test per workspace.
-    // Delete this dummy test once we have an actual real test.
     #[test]
-    fn dummy_test_until_we_have_a_real_test() {}
+    fn pencil_contacts_are_pens() {
+        let pencil = |phase, altitude_angle| {
+            WindowEvent::Touch(Touch {
+                device_id: winit::event::DeviceId::dummy(),
+                phase,
+                location: winit::dpi::PhysicalPosition::new(0.0, 0.0),
+                force: Some(Force::Calibrated {
+                    force: 1.0,
+                    max_possible_force: 4.0,
+                    altitude_angle,
+                }),
+                id: 7,
+            })
+        };
+        let mut reducer = WindowEventReducer::default();
+
+        let PointerEvent::Down(down) =
+            pointer_event(reducer.reduce(1.0, &pencil(TouchPhase::Started, Some(0.5))))
+        else {
+            panic!("expected a down");
+        };
+        assert_eq!(down.pointer.pointer_type, PointerType::Pen);
+        assert_eq!(down.pointer.pointer_id, Some(PointerId::PRIMARY));
+        assert_eq!(down.button, Some(PointerButton::Primary));
+        assert!(down.state.buttons.contains(PointerButton::Primary));
+        assert_eq!(down.state.orientation.altitude, 0.5);
+        assert_eq!(down.state.pressure, 0.25);
+
+        // The contact stays a pen even if a later force lacks the altitude.
+        let PointerEvent::Up(up) =
+            pointer_event(reducer.reduce(1.0, &pencil(TouchPhase::Ended, None)))
+        else {
+            panic!("expected an up");
+        };
+        assert_eq!(up.pointer.pointer_type, PointerType::Pen);
+        assert_eq!(up.button, Some(PointerButton::Primary));
+        assert!(up.state.buttons.is_empty());
+        assert_eq!(up.state.pressure, 0.0);
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support routines for converting pen data from [`winit`].
+//!
+//! This follows what winit 0.30 exposes. Pens arrive as [`Touch`] events,
+//! and only the Apple Pencil on iOS is told apart from a finger, by the
+//! `altitude_angle` of [`Force::Calibrated`].
+//!
+//! Winit 0.30 has no tablet tool events, so the eraser and barrel buttons of a pen
+//! cannot be reported as [`PointerButton::PenEraser`] and [`PointerButton::Secondary`]
+//! as they are by `ui-events-web`, and pen contacts only ever press
+//! [`PointerButton::Primary`].
+//! Tilt azimuth and twist are not available either, and keep their defaults.
+//!
+//! [`Touch`]: winit::event::Touch
+//! [`PointerButton::PenEraser`]: ui_events::pointer::PointerButton::PenEraser
+//! [`PointerButton::Secondary`]: ui_events::pointer::PointerButton::Secondary
+//! [`PointerButton::Primary`]: ui_events::pointer::PointerButton::Primary
+
+use ui_events::pointer::{PointerOrientation, PointerType};
+use winit::event::Force;
+
+/// Determine the [`PointerType`] of a touch contact from its [`Force`].
+///
+/// This is [`PointerType::Pen`] when the force carries a stylus altitude,
+/// and [`PointerType::Touch`] otherwise.
+pub fn pointer_type_from_force(force: Option<Force>) -> PointerType {
+    match force {
+        Some(Force::Calibrated {
+            altitude_angle: Some(_),
+            ..
+        }) => PointerType::Pen,
+        _ => PointerType::Touch,
+    }
+}
+
+/// Make a [`PointerOrientation`] from the stylus altitude of a [`Force`], if any.
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "Angles do not need more than f32 precision."
+)]
+pub fn orientation_from_force(force: Force) -> Option<PointerOrientation> {
+    match force {
+        Force::Calibrated {
+            altitude_angle: Some(altitude),
+            ..
+        } => Some(PointerOrientation {
+            altitude: (altitude as f32).clamp(0.0, core::f32::consts::FRAC_PI_2),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+/// Normalized pressure of a pen from its [`Force`], in the range `[0, 1]`.
+///
+/// Unlike [`Force::normalized`], this does not correct for the altitude, as
+/// pen pressure is conventionally measured along the pen.
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "Pressure does not need more than f32 precision."
+)]
+pub fn pen_pressure_from_force(force: Force) -> f32 {
+    let pressure = match force {
+        Force::Calibrated {
+            force,
+            max_possible_force,
+            ..
+        } if max_possible_force > 0.0 => force / max_possible_force,
+        Force::Calibrated { .. } => 0.5,
+        Force::Normalized(q) => q,
+    };
+    (pressure as f32).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pencil(force: f64, altitude_angle: Option<f64>) -> Force {
+        Force::Calibrated {
+            force,
+            max_possible_force: 4.0,
+            altitude_angle,
+        }
+    }
+
+    #[test]
+    fn pencil_is_a_pen() {
+        assert_eq!(pointer_type_from_force(None), PointerType::Touch);
+        assert_eq!(
+            pointer_type_from_force(Some(Force::Normalized(0.5))),
+            PointerType::Touch
+        );
+        assert_eq!(
+            pointer_type_from_force(Some(pencil(1.0, None))),
+            PointerType::Touch
+        );
+        assert_eq!(
+            pointer_type_from_force(Some(pencil(1.0, Some(0.5)))),
+            PointerType::Pen
+        );
+    }
+
+    #[test]
+    fn orientation_and_pressure() {
+        assert_eq!(orientation_from_force(pencil(1.0, None)), None);
+        let o = orientation_from_force(pencil(1.0, Some(0.5))).unwrap();
+        assert_eq!(o.altitude, 0.5);
+        assert_eq!(o.azimuth, PointerOrientation::default().azimuth);
+
+        assert_eq!(pen_pressure_from_force(pencil(1.0, Some(0.5))), 0.25);
+        assert_eq!(pen_pressure_from_force(pencil(8.0, Some(0.5))), 1.0);
+        assert_eq!(pen_pressure_from_force(Force::Normalized(0.75)), 0.75);
+    }
+}
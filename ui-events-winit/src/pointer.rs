@@ -3,8 +3,19 @@
 
 //! Support routines for converting pointer data from [`winit`].
 
-use ui_events::pointer::PointerButton;
-use winit::event::MouseButton;
+use ui_events::{ScrollDelta, ScrollSource, pointer::PointerButton};
+use winit::event::{MouseButton, MouseScrollDelta};
+
+/// Make a [`ScrollDelta`] and its likely [`ScrollSource`] from a [`MouseScrollDelta`].
+///
+/// Line deltas come from notched wheels, and pixel deltas from touchpads
+/// and other precise devices.
+pub fn from_winit_scroll_delta(delta: MouseScrollDelta) -> (ScrollDelta, ScrollSource) {
+    match delta {
+        MouseScrollDelta::LineDelta(x, y) => (ScrollDelta::LineDelta(x, y), ScrollSource::Wheel),
+        MouseScrollDelta::PixelDelta(p) => (ScrollDelta::PixelDelta(p), ScrollSource::Touchpad),
+    }
+}
 
 /// Try to make a [`PointerButton`] from a [`MouseButton`].
 ///
@@ -0,0 +1,324 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Routing of [`WindowEvent`]s from several windows.
+//!
+//! [`WindowEventRouter`] keeps a [`WindowEventReducer`] for each window, and shares
+//! what is not specific to a window between them.
+
+extern crate alloc;
+use alloc::collections::BTreeMap;
+
+use ui_events::pointer::{ClickCountConfig, PersistentDeviceId, PointerButtons, PredictionConfig};
+use winit::event::{DeviceEvent, DeviceId, WindowEvent};
+use winit::keyboard::ModifiersState;
+use winit::window::WindowId;
+
+use crate::clock::ClockSource;
+use crate::device::{DeviceInfo, DeviceTable};
+use crate::{Clock, DeviceEventTranslation, WindowEventReducer, WindowEventTranslation, keyboard};
+
+/// Manages a [`WindowEventReducer`] for each window of an application.
+///
+/// Call [`WindowEventRouter::reduce`] with every [`WindowEvent`] and the [`WindowId`]
+/// it was delivered for. A reducer is made for each window the first time it has
+/// an event, and dropped on [`WindowEvent::Destroyed`].
+///
+/// All windows share:
+///  - the keyboard modifiers, which winit only reports to the focused window,
+///  - the [`Clock`] giving [`PointerState::time`], so times compare across windows,
+///  - the [`PersistentDeviceId`]s of devices, and
+///  - the buttons held on the mouse, which move to the window the cursor moves into.
+///
+/// Click counts are kept per window, as positions in different windows are unrelated.
+///
+/// [`PointerState::time`]: ui_events::pointer::PointerState::time
+#[derive(Debug, Default)]
+pub struct WindowEventRouter {
+    /// Reducer of each window.
+    windows: BTreeMap<WindowId, WindowEventReducer>,
+    /// State of modifiers, across windows.
+    modifiers: ModifiersState,
+    /// Devices seen so far, across windows.
+    devices: DeviceTable,
+    /// Window the mouse cursor was last in.
+    pointer_window: Option<WindowId>,
+    /// Click count configuration for new reducers.
+    click_count_config: ClickCountConfig,
+    /// Prediction configuration for new reducers.
+    prediction_config: Option<PredictionConfig>,
+    /// Source of event times.
+    clock: ClockSource,
+}
+
+impl WindowEventRouter {
+    /// Set how clicks and taps are counted, in every window.
+    ///
+    /// See [`WindowEventReducer::set_click_count_config`].
+    pub fn set_click_count_config(&mut self, config: ClickCountConfig) {
+        self.click_count_config = config;
+        for reducer in self.windows.values_mut() {
+            reducer.set_click_count_config(config);
+        }
+    }
+
+    /// Enable or disable motion prediction, in every window.
+    ///
+    /// See [`WindowEventReducer::set_motion_prediction`].
+    pub fn set_motion_prediction(&mut self, config: Option<PredictionConfig>) {
+        self.prediction_config = config;
+        for reducer in self.windows.values_mut() {
+            reducer.set_motion_prediction(config);
+        }
+    }
+
+    /// Set the [`Clock`] that [`WindowEventRouter::reduce`] reads event times from.
+    ///
+    /// The times are passed on to the reducer of each window, so there is one
+    /// time base for all of them. By default, this is an [`InstantClock`].
+    ///
+    /// [`InstantClock`]: crate::InstantClock
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock.set(clock);
+    }
+
+    /// The windows that currently have a reducer.
+    pub fn windows(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// Drop the reducer of a window.
+    ///
+    /// This happens automatically on [`WindowEvent::Destroyed`].
+    pub fn remove_window(&mut self, window_id: WindowId) {
+        self.windows.remove(&window_id);
+        if self.pointer_window == Some(window_id) {
+            self.pointer_window = None;
+        }
+    }
+
+    /// The [`PersistentDeviceId`] given to a winit [`DeviceId`], if it has been seen.
+    pub fn persistent_device_id(&self, device_id: DeviceId) -> Option<PersistentDeviceId> {
+        self.devices.persistent_device_id(device_id)
+    }
+
+    /// The device with the given [`PersistentDeviceId`], if any.
+    pub fn device(&self, id: PersistentDeviceId) -> Option<&DeviceInfo> {
+        self.devices.get(id)
+    }
+
+    /// All devices seen so far, in order of appearance.
+    pub fn devices(&self) -> impl Iterator<Item = &DeviceInfo> {
+        self.devices.iter()
+    }
+
+    /// Process a [`WindowEvent`] for a window, returning the resulting translations in order.
+    ///
+    /// See [`WindowEventReducer::reduce`].
+    pub fn reduce(
+        &mut self,
+        window_id: WindowId,
+        scale_factor: f64,
+        we: &WindowEvent,
+    ) -> impl Iterator<Item = (WindowId, WindowEventTranslation)> + use<> {
+        let time = self.clock.now();
+        self.reduce_at(window_id, time, scale_factor, we)
+    }
+
+    /// Process a [`WindowEvent`] for a window, that happened at `time`.
+    ///
+    /// See [`WindowEventReducer::reduce_at`].
+    pub fn reduce_at(
+        &mut self,
+        window_id: WindowId,
+        time: u64,
+        scale_factor: f64,
+        we: &WindowEvent,
+    ) -> impl Iterator<Item = (WindowId, WindowEventTranslation)> + use<> {
+        self.route(window_id, we, |reducer| {
+            reducer.reduce_at(time, scale_factor, we)
+        })
+        .unwrap_or_default()
+        .map(move |translation| (window_id, translation))
+    }
+
+    /// Process a [`DeviceEvent`].
+    ///
+    /// This is given to the reducer of the window the mouse cursor was last in,
+    /// or else of any window. It returns `None` while there are no windows.
+    ///
+    /// See [`WindowEventReducer::reduce_device_event`].
+    pub fn reduce_device_event(
+        &mut self,
+        device_id: DeviceId,
+        de: &DeviceEvent,
+    ) -> Option<DeviceEventTranslation> {
+        let time = self.clock.now();
+        self.reduce_device_event_at(time, device_id, de)
+    }
+
+    /// Process a [`DeviceEvent`] that happened at `time`.
+    ///
+    /// See [`WindowEventRouter::reduce_device_event`] and
+    /// [`WindowEventReducer::reduce_device_event_at`].
+    pub fn reduce_device_event_at(
+        &mut self,
+        time: u64,
+        device_id: DeviceId,
+        de: &DeviceEvent,
+    ) -> Option<DeviceEventTranslation> {
+        let window_id = self
+            .pointer_window
+            .or_else(|| self.windows.keys().next().copied())?;
+        self.with_reducer(window_id, |reducer| {
+            reducer.reduce_device_event_at(time, device_id, de)
+        })
+    }
+
+    /// Run `f` on the reducer of `window_id` for `we`, with the shared state.
+    ///
+    /// Returns `None` if the window was destroyed.
+    fn route<R>(
+        &mut self,
+        window_id: WindowId,
+        we: &WindowEvent,
+        f: impl FnOnce(&mut WindowEventReducer) -> R,
+    ) -> Option<R> {
+        if matches!(we, WindowEvent::Destroyed) {
+            self.remove_window(window_id);
+            return None;
+        }
+
+        // Buttons held on the mouse follow it into another window.
+        let mut buttons = PointerButtons::default();
+        if matches!(
+            we,
+            WindowEvent::CursorEntered { .. }
+                | WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+        ) && self.pointer_window != Some(window_id)
+        {
+            if let Some(previous) = self.pointer_window.and_then(|id| self.windows.get_mut(&id)) {
+                buttons = core::mem::take(&mut previous.primary_state.buttons);
+            }
+            self.pointer_window = Some(window_id);
+        }
+        Some(self.with_reducer(window_id, |reducer| {
+            reducer.primary_state.buttons.extend(buttons);
+            f(reducer)
+        }))
+    }
+
+    /// Run `f` on the reducer of `window_id`, making it if needed, with the shared state.
+    fn with_reducer<R>(
+        &mut self,
+        window_id: WindowId,
+        f: impl FnOnce(&mut WindowEventReducer) -> R,
+    ) -> R {
+        let (click_count_config, prediction_config) =
+            (self.click_count_config, self.prediction_config);
+        let reducer = self.windows.entry(window_id).or_insert_with(|| {
+            let mut reducer = WindowEventReducer::default();
+            reducer.set_click_count_config(click_count_config);
+            reducer.set_motion_prediction(prediction_config);
+            reducer
+        });
+        // Modifiers are only reported to the focused window.
+        reducer.modifiers = self.modifiers;
+        reducer.primary_state.modifiers = keyboard::from_winit_modifier_state(self.modifiers);
+
+        core::mem::swap(&mut reducer.devices, &mut self.devices);
+        let result = f(reducer);
+        core::mem::swap(&mut reducer.devices, &mut self.devices);
+        self.modifiers = reducer.modifiers;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use ui_events::keyboard::Modifiers;
+    use ui_events::pointer::{PointerButton, PointerEvent, PointerState};
+    use winit::event::{ElementState, MouseButton};
+
+    fn pointer_state(
+        translations: impl Iterator<Item = (WindowId, WindowEventTranslation)>,
+    ) -> (u64, PointerState) {
+        let Ok([(window_id, WindowEventTranslation::Pointer(e))]) =
+            <[_; 1]>::try_from(translations.collect::<Vec<_>>())
+        else {
+            panic!("expected a pointer event");
+        };
+        let state = match e {
+            PointerEvent::Down(e) | PointerEvent::Up(e) => e.state,
+            PointerEvent::Move(e) => e.current,
+            e => panic!("unexpected {e:?}"),
+        };
+        (window_id.into(), state)
+    }
+
+    #[test]
+    fn shares_state_across_windows() {
+        let (w1, w2) = (WindowId::from(1), WindowId::from(2));
+        let device_id = DeviceId::dummy();
+        let mouse = |state| WindowEvent::MouseInput {
+            device_id,
+            state,
+            button: MouseButton::Left,
+        };
+        let moved = WindowEvent::CursorMoved {
+            device_id,
+            position: (1.0, 2.0).into(),
+        };
+        let mut router = WindowEventRouter::default();
+
+        let shift = WindowEvent::ModifiersChanged(ModifiersState::SHIFT.into());
+        assert!(router.reduce(w1, 1.0, &shift).next().is_none());
+        let (w, down) = pointer_state(router.reduce(w1, 1.0, &mouse(ElementState::Pressed)));
+        assert_eq!(w, 1);
+        assert!(down.buttons.contains(PointerButton::Primary));
+
+        // Dragging into another window keeps the button and modifiers.
+        assert!(matches!(
+            router
+                .reduce(w2, 1.0, &WindowEvent::CursorEntered { device_id })
+                .collect::<Vec<_>>()
+                .as_slice(),
+            [(w, WindowEventTranslation::Pointer(PointerEvent::Enter(..)))] if *w == w2
+        ));
+        let (w, drag) = pointer_state(router.reduce(w2, 1.0, &moved));
+        assert_eq!(w, 2);
+        assert!(drag.buttons.contains(PointerButton::Primary));
+        assert_eq!(drag.modifiers, Modifiers::SHIFT);
+        assert!(drag.time >= down.time);
+
+        let (_, up) = pointer_state(router.reduce(w2, 1.0, &mouse(ElementState::Released)));
+        assert!(up.buttons.is_empty());
+        // The first window no longer thinks the button is held.
+        let (w, back) = pointer_state(router.reduce(w1, 1.0, &moved));
+        assert_eq!(w, 1);
+        assert!(back.buttons.is_empty());
+
+        // Raw motion goes to the window the cursor is in.
+        let motion = DeviceEvent::MouseMotion { delta: (1.0, 0.0) };
+        let Some(DeviceEventTranslation::Motion(e)) =
+            router.reduce_device_event(device_id, &motion)
+        else {
+            panic!("expected motion");
+        };
+        assert_eq!(e.state.modifiers, Modifiers::SHIFT);
+        assert!(e.state.time >= back.time);
+
+        assert_eq!(router.devices().count(), 1);
+        assert_eq!(router.windows().count(), 2);
+        assert!(
+            router
+                .reduce(w1, 1.0, &WindowEvent::Destroyed)
+                .next()
+                .is_none()
+        );
+        assert_eq!(router.windows().collect::<Vec<_>>(), [w2]);
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support routines for converting IME data from [`winit`]
+
+use ui_events::text_input::ImeEvent;
+use winit::event::Ime;
+
+/// Convert a [`winit::event::Ime`] to an [`ImeEvent`].
+pub fn from_winit_ime(ime: Ime) -> ImeEvent {
+    match ime {
+        Ime::Enabled => ImeEvent::Enabled,
+        Ime::Preedit(text, cursor) => ImeEvent::Preedit {
+            text,
+            cursor: cursor.map(|(start, end)| start..end),
+        },
+        Ime::Commit(text) => ImeEvent::Commit(text),
+        Ime::Disabled => ImeEvent::Disabled,
+    }
+}
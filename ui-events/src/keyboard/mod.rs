@@ -17,8 +17,13 @@
 //!     (mods.ctrl() || mods.meta()) && key == Key::Character("c".into())
 //! }
 //! ```
+//!
+//! For anything beyond a handful of shortcuts, use the [`shortcut`] module,
+//! which parses strings like `"Mod+C"` and detects conflicting bindings.
 
 pub use keyboard_types::*;
 
 #[cfg(target_os = "android")]
 pub mod android;
+
+pub mod shortcut;
@@ -0,0 +1,887 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Keyboard shortcuts
+//!
+//! This module parses human readable shortcuts, such as `"Ctrl+Shift+Z"`,
+//! `"Alt+ArrowLeft"`, or the two step sequence `"Mod+K Mod+S"`, and matches
+//! them against [`KeyboardEvent`]s.
+//!
+//! - A [`KeyChord`] is a set of modifiers plus one key.
+//! - A [`Shortcut`] is a sequence of one or more chords, separated by spaces.
+//! - A [`ShortcutTable`] maps shortcuts to actions, rejects conflicting bindings,
+//!   and tracks progress through multi-chord sequences.
+//!
+//! ## Syntax
+//!
+//! Chords are written as modifiers followed by a key, joined with `+`.
+//! Names are case-insensitive.
+//!
+//! - `Ctrl`/`Control`, `Shift`, `Alt`/`Option`, and `Meta`/`Cmd`/`Command`/`Super`.
+//! - `Mod`, which is `Meta` on Apple platforms and `Control` elsewhere.
+//!   Override this with [`ParseOptions::with_mod`], for example on the web.
+//! - A key is either a single character (`Z`, `1`, `/`, `+`), `Space`, `Esc`,
+//!   or a [`NamedKey`] such as `ArrowLeft`, `Enter`, or `F5`.
+//!   When matching by [`Code`], the key may also be any [`Code`] name, such as `KeyZ`.
+//!
+//! ## Matching
+//!
+//! Chords match key presses (including repeats) whose modifiers are exactly the
+//! chord's, ignoring lock modifiers such as Caps Lock. Character keys are compared
+//! without regard to ASCII case, so `Ctrl+Shift+Z` matches whether the platform
+//! reports `z` or `Z`. Shift is not implied by a character, so a shortcut for `?`
+//! on a US layout must be written `Shift+?`.
+//!
+//! By default chords match the logical [`Key`], which follows the user's layout.
+//! Use [`MatchBy::Code`] to match physical key positions instead, which suits
+//! games and shortcuts chosen for their position, like `WASD`.
+//!
+//! ## Example
+//!
+//! ```
+//! use ui_events::keyboard::shortcut::{Shortcut, ShortcutTable};
+//!
+//! let mut table = ShortcutTable::new();
+//! table.insert("Mod+Z".parse().unwrap(), "undo").unwrap();
+//! table.insert("Mod+Shift+Z".parse().unwrap(), "redo").unwrap();
+//! table.insert("Mod+K Mod+S".parse().unwrap(), "save all").unwrap();
+//!
+//! // Conflicting bindings are reported rather than silently shadowed.
+//! assert!(table.insert("Mod+K".parse().unwrap(), "oops").is_err());
+//!
+//! # let events: Vec<ui_events::keyboard::KeyboardEvent> = vec![];
+//! for e in &events {
+//!     if let Some(action) = table.process_keyboard_event(e) {
+//!         // Perform `action`.
+//!         let _ = action;
+//!     }
+//! }
+//! ```
+
+extern crate alloc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use super::{Code, Key, KeyState, KeyboardEvent, Modifiers, NamedKey};
+
+/// Modifiers that are significant when matching a [`KeyChord`].
+///
+/// Lock modifiers, `AltGraph`, `Fn`, and the legacy modifiers are ignored.
+const SIGNIFICANT_MODIFIERS: Modifiers = Modifiers::CONTROL
+    .union(Modifiers::SHIFT)
+    .union(Modifiers::ALT)
+    .union(Modifiers::META);
+
+/// What a [`KeyChord`] compares against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MatchBy {
+    /// Match the logical [`Key`], which depends on the keyboard layout.
+    #[default]
+    Key,
+    /// Match the physical [`Code`], regardless of the keyboard layout.
+    Code,
+}
+
+/// The key part of a [`KeyChord`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChordKey {
+    /// A logical key.
+    Key(Key),
+    /// A physical key.
+    Code(Code),
+}
+
+/// A set of modifiers held while pressing one key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// Modifiers that must be held, and no others.
+    pub modifiers: Modifiers,
+    /// The key to press.
+    pub key: ChordKey,
+}
+
+impl KeyChord {
+    /// A chord matching the logical `key`.
+    pub fn key(modifiers: Modifiers, key: Key) -> Self {
+        Self {
+            modifiers,
+            key: ChordKey::Key(key),
+        }
+    }
+
+    /// A chord matching the physical `code`.
+    pub fn code(modifiers: Modifiers, code: Code) -> Self {
+        Self {
+            modifiers,
+            key: ChordKey::Code(code),
+        }
+    }
+
+    /// Return `true` if pressing `key` at `code` with `modifiers` held matches this chord.
+    pub fn matches_key(&self, key: &Key, code: Code, modifiers: Modifiers) -> bool {
+        if (modifiers & SIGNIFICANT_MODIFIERS) != (self.modifiers & SIGNIFICANT_MODIFIERS) {
+            return false;
+        }
+        match &self.key {
+            ChordKey::Key(k) => keys_match(k, key),
+            ChordKey::Code(c) => *c == code,
+        }
+    }
+
+    /// Return `true` if `event` is a key press (or repeat) matching this chord.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        event.state == KeyState::Down && self.matches_key(&event.key, event.code, event.modifiers)
+    }
+
+    /// Return `true` if both chords describe the same key press.
+    fn same_as(&self, other: &Self) -> bool {
+        (self.modifiers & SIGNIFICANT_MODIFIERS) == (other.modifiers & SIGNIFICANT_MODIFIERS)
+            && match (&self.key, &other.key) {
+                (ChordKey::Key(a), ChordKey::Key(b)) => keys_match(a, b),
+                (ChordKey::Code(a), ChordKey::Code(b)) => a == b,
+                (ChordKey::Key(k), ChordKey::Code(c)) | (ChordKey::Code(c), ChordKey::Key(k)) => {
+                    us_layout_code(k) == Some(*c)
+                }
+            }
+    }
+}
+
+/// The [`Code`] of an ASCII letter or digit key.
+fn code_for_char(c: char) -> Option<Code> {
+    let name = if c.is_ascii_digit() {
+        let mut s = String::from("Digit");
+        s.push(c);
+        s
+    } else if c.is_ascii_alphabetic() {
+        let mut s = String::from("Key");
+        s.push(c.to_ascii_uppercase());
+        s
+    } else {
+        return None;
+    };
+    Code::from_str(&name).ok()
+}
+
+/// The [`Code`] that produces `key` on a US layout, for letters, digits, space,
+/// and named keys that have a code of the same name.
+fn us_layout_code(key: &Key) -> Option<Code> {
+    match key {
+        Key::Character(s) if s == " " => Some(Code::Space),
+        Key::Character(s) => {
+            let mut chars = s.chars();
+            chars
+                .next()
+                .filter(|_| chars.next().is_none())
+                .and_then(code_for_char)
+        }
+        Key::Named(k) => Code::from_str(&k.to_string()).ok(),
+    }
+}
+
+fn keys_match(a: &Key, b: &Key) -> bool {
+    match (a, b) {
+        (Key::Character(a), Key::Character(b)) => a.eq_ignore_ascii_case(b),
+        _ => a == b,
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::META, "Meta"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match &self.key {
+            ChordKey::Key(Key::Character(c)) if c == " " => f.write_str("Space"),
+            ChordKey::Key(Key::Character(c)) => {
+                for ch in c.chars() {
+                    write!(f, "{}", ch.to_ascii_uppercase())?;
+                }
+                Ok(())
+            }
+            ChordKey::Key(k) => write!(f, "{k}"),
+            ChordKey::Code(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+/// Options controlling how shortcut strings are parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Modifier that `Mod` stands for.
+    pub mod_modifier: Modifiers,
+    /// Whether keys are matched by [`Key`] or [`Code`].
+    pub match_by: MatchBy,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            mod_modifier: if cfg!(any(target_os = "macos", target_os = "ios")) {
+                Modifiers::META
+            } else {
+                Modifiers::CONTROL
+            },
+            match_by: MatchBy::Key,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Set the modifier that `Mod` stands for (builder style).
+    ///
+    /// The default is [`Modifiers::META`] on Apple platforms and
+    /// [`Modifiers::CONTROL`] elsewhere. Platforms such as the web
+    /// can only determine this at runtime.
+    pub fn with_mod(mut self, modifier: Modifiers) -> Self {
+        self.mod_modifier = modifier;
+        self
+    }
+
+    /// Set whether keys are matched by [`Key`] or [`Code`] (builder style).
+    pub fn with_match_by(mut self, match_by: MatchBy) -> Self {
+        self.match_by = match_by;
+        self
+    }
+
+    fn parse_modifier(self, name: &str) -> Option<Modifiers> {
+        const NAMES: &[(&str, Modifiers)] = &[
+            ("Ctrl", Modifiers::CONTROL),
+            ("Control", Modifiers::CONTROL),
+            ("Shift", Modifiers::SHIFT),
+            ("Alt", Modifiers::ALT),
+            ("Option", Modifiers::ALT),
+            ("Meta", Modifiers::META),
+            ("Cmd", Modifiers::META),
+            ("Command", Modifiers::META),
+            ("Super", Modifiers::META),
+        ];
+        if name.eq_ignore_ascii_case("Mod") {
+            return Some(self.mod_modifier);
+        }
+        NAMES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, m)| *m)
+    }
+
+    fn parse_key(self, name: &str) -> Option<ChordKey> {
+        let mut chars = name.chars();
+        let single = chars.next().filter(|_| chars.next().is_none());
+        match self.match_by {
+            MatchBy::Key => {
+                if name.eq_ignore_ascii_case("Space") {
+                    return Some(ChordKey::Key(Key::Character(" ".into())));
+                }
+                if name.eq_ignore_ascii_case("Esc") {
+                    return Some(ChordKey::Key(Key::Named(NamedKey::Escape)));
+                }
+                if let Some(c) = single {
+                    return Some(ChordKey::Key(Key::Character(
+                        c.to_ascii_lowercase().to_string(),
+                    )));
+                }
+                NamedKey::from_str(name)
+                    .ok()
+                    .map(|k| ChordKey::Key(Key::Named(k)))
+            }
+            MatchBy::Code => {
+                if name.eq_ignore_ascii_case("Esc") {
+                    return Some(ChordKey::Code(Code::Escape));
+                }
+                if name.eq_ignore_ascii_case("Space") {
+                    return Some(ChordKey::Code(Code::Space));
+                }
+                if let Some(code) = single.and_then(code_for_char) {
+                    return Some(ChordKey::Code(code));
+                }
+                Code::from_str(name).ok().map(ChordKey::Code)
+            }
+        }
+    }
+
+    /// Parse a single chord, such as `Ctrl+Shift+Z`.
+    pub fn parse_chord(&self, s: &str) -> Result<KeyChord, ParseShortcutError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        // A trailing `+` is the key itself, as in `Ctrl++`.
+        let (modifiers, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(m) = s.strip_suffix("++") {
+            (m, "+")
+        } else if s.ends_with('+') {
+            return Err(ParseShortcutError::MissingKey(s.into()));
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let mut mods = Modifiers::empty();
+        if !modifiers.is_empty() {
+            for name in modifiers.split('+') {
+                let m = self
+                    .parse_modifier(name.trim())
+                    .ok_or_else(|| ParseShortcutError::UnknownModifier(name.into()))?;
+                mods.insert(m);
+            }
+        }
+
+        let key = self
+            .parse_key(key.trim())
+            .ok_or_else(|| ParseShortcutError::UnknownKey(key.into()))?;
+        Ok(KeyChord {
+            modifiers: mods,
+            key,
+        })
+    }
+
+    /// Parse a shortcut made of one or more chords separated by whitespace,
+    /// such as `Mod+K Mod+S`.
+    pub fn parse(&self, s: &str) -> Result<Shortcut, ParseShortcutError> {
+        let chords = s
+            .split_whitespace()
+            .map(|c| self.parse_chord(c))
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(ParseShortcutError::Empty);
+        }
+        Ok(Shortcut { chords })
+    }
+}
+
+/// An error parsing a [`Shortcut`] or [`KeyChord`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseShortcutError {
+    /// The string contains no chords.
+    Empty,
+    /// A chord has modifiers but no key.
+    MissingKey(String),
+    /// A modifier name was not recognized.
+    UnknownModifier(String),
+    /// A key name was not recognized.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("shortcut is empty"),
+            Self::MissingKey(c) => write!(f, "chord `{c}` has no key"),
+            Self::UnknownModifier(m) => write!(f, "unknown modifier `{m}`"),
+            Self::UnknownKey(k) => write!(f, "unknown key `{k}`"),
+        }
+    }
+}
+
+impl core::error::Error for ParseShortcutError {}
+
+/// A sequence of one or more [`KeyChord`]s.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// The chords, in the order they must be pressed. Never empty.
+    chords: Vec<KeyChord>,
+}
+
+impl Shortcut {
+    /// Parse a shortcut with the given options.
+    ///
+    /// [`str::parse`] uses [`ParseOptions::default`].
+    pub fn parse_with(s: &str, options: &ParseOptions) -> Result<Self, ParseShortcutError> {
+        options.parse(s)
+    }
+
+    /// The chords, in the order they must be pressed.
+    pub fn chords(&self) -> &[KeyChord] {
+        &self.chords
+    }
+
+    /// Return `true` if this shortcut is a single chord matching `event`.
+    ///
+    /// Use a [`ShortcutTable`] to match multi-chord sequences.
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        matches!(&self.chords[..], [chord] if chord.matches(event))
+    }
+
+    /// Return `true` if the chords of `self` start with all the chords of `other`.
+    fn starts_with(&self, other: &Self) -> bool {
+        self.chords.len() >= other.chords.len()
+            && self
+                .chords
+                .iter()
+                .zip(&other.chords)
+                .all(|(a, b)| a.same_as(b))
+    }
+}
+
+impl From<KeyChord> for Shortcut {
+    fn from(chord: KeyChord) -> Self {
+        Self {
+            chords: alloc::vec![chord],
+        }
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParseOptions::default().parse(s)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, chord) in self.chords.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{chord}")?;
+        }
+        Ok(())
+    }
+}
+
+/// How a new binding conflicts with an existing one in a [`ShortcutTable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both bindings use the same shortcut.
+    Same,
+    /// The existing shortcut is the start of the new one, so the new one
+    /// could never be completed.
+    ExistingIsPrefix,
+    /// The new shortcut is the start of the existing one, so the existing one
+    /// could never be completed.
+    NewIsPrefix,
+}
+
+/// A binding rejected by [`ShortcutTable::insert`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    /// The existing shortcut that conflicts with the new one.
+    pub existing: Shortcut,
+    /// The shortcut that was rejected.
+    pub new: Shortcut,
+    /// How they conflict.
+    pub kind: ConflictKind,
+}
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            existing,
+            new,
+            kind,
+        } = self;
+        match kind {
+            ConflictKind::Same => write!(f, "`{new}` is already bound"),
+            ConflictKind::ExistingIsPrefix => {
+                write!(
+                    f,
+                    "`{new}` can never be completed, because `{existing}` is bound"
+                )
+            }
+            ConflictKind::NewIsPrefix => {
+                write!(f, "`{new}` would prevent completing `{existing}`")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ShortcutConflict {}
+
+/// A set of [`Shortcut`]s bound to actions.
+///
+/// Feed key events to [`ShortcutTable::process_keyboard_event`], which
+/// returns the action of any shortcut they complete.
+#[derive(Clone, Debug)]
+pub struct ShortcutTable<A> {
+    /// Bindings, in insertion order.
+    bindings: Vec<(Shortcut, A)>,
+    /// Chords pressed so far in a sequence that is not yet complete.
+    pending: Vec<KeyboardEvent>,
+}
+
+impl<A> Default for ShortcutTable<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<A> ShortcutTable<A> {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `shortcut` to `action`.
+    ///
+    /// Fails without changing the table if `shortcut` is already bound, or if
+    /// one of `shortcut` and an existing shortcut is the start of the other.
+    ///
+    /// A chord matching a [`Key`] and a chord matching a [`Code`] are taken to be the
+    /// same if the code produces the key on a US layout, such as `Ctrl+Z` and `Ctrl+KeyZ`.
+    /// On other layouts they may differ, but binding both is almost always a mistake.
+    pub fn insert(&mut self, shortcut: Shortcut, action: A) -> Result<(), ShortcutConflict> {
+        for (existing, _) in &self.bindings {
+            let kind = if shortcut.starts_with(existing) {
+                if existing.chords.len() == shortcut.chords.len() {
+                    ConflictKind::Same
+                } else {
+                    ConflictKind::ExistingIsPrefix
+                }
+            } else if existing.starts_with(&shortcut) {
+                ConflictKind::NewIsPrefix
+            } else {
+                continue;
+            };
+            return Err(ShortcutConflict {
+                existing: existing.clone(),
+                new: shortcut,
+                kind,
+            });
+        }
+        self.bindings.push((shortcut, action));
+        Ok(())
+    }
+
+    /// Remove the binding for `shortcut`, returning its action.
+    pub fn remove(&mut self, shortcut: &Shortcut) -> Option<A> {
+        let idx = self.bindings.iter().position(|(s, _)| s == shortcut)?;
+        self.pending.clear();
+        Some(self.bindings.remove(idx).1)
+    }
+
+    /// The action bound to `shortcut`, if any.
+    pub fn get(&self, shortcut: &Shortcut) -> Option<&A> {
+        self.bindings
+            .iter()
+            .find(|(s, _)| s == shortcut)
+            .map(|(_, a)| a)
+    }
+
+    /// Iterate over the bindings, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Shortcut, &A)> {
+        self.bindings.iter().map(|(s, a)| (s, a))
+    }
+
+    /// Number of bindings.
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Return `true` if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Return `true` if the chords pressed so far are the start of a longer shortcut.
+    ///
+    /// Use this to show that a sequence is in progress.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Abandon a sequence in progress, for example after a timeout or loss of focus.
+    pub fn reset_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Update the table with a key event, and return the action of any shortcut it completes.
+    ///
+    /// Key releases, presses of modifier keys, and presses consumed by an IME composition
+    /// (with [`KeyboardEvent::is_composing`] set) are ignored. A press that does not continue
+    /// a sequence in progress abandons it, and is then matched on its own.
+    pub fn process_keyboard_event(&mut self, event: &KeyboardEvent) -> Option<&A> {
+        if event.state != KeyState::Down || event.is_composing || is_modifier_key(&event.key) {
+            return None;
+        }
+        self.pending.push(event.clone());
+        loop {
+            let mut prefix = false;
+            for (idx, (shortcut, _)) in self.bindings.iter().enumerate() {
+                if shortcut.chords.len() < self.pending.len()
+                    || !shortcut
+                        .chords
+                        .iter()
+                        .zip(&self.pending)
+                        .all(|(c, e)| c.matches(e))
+                {
+                    continue;
+                }
+                if shortcut.chords.len() == self.pending.len() {
+                    self.pending.clear();
+                    return Some(&self.bindings[idx].1);
+                }
+                prefix = true;
+            }
+            if prefix {
+                return None;
+            }
+            if self.pending.len() == 1 {
+                self.pending.clear();
+                return None;
+            }
+            // Retry with only the latest press.
+            self.pending.drain(..self.pending.len() - 1);
+        }
+    }
+}
+
+fn is_modifier_key(key: &Key) -> bool {
+    matches!(
+        key,
+        Key::Named(
+            NamedKey::Alt
+                | NamedKey::AltGraph
+                | NamedKey::CapsLock
+                | NamedKey::Control
+                | NamedKey::Fn
+                | NamedKey::FnLock
+                | NamedKey::Meta
+                | NamedKey::NumLock
+                | NamedKey::ScrollLock
+                | NamedKey::Shift
+                | NamedKey::Symbol
+                | NamedKey::SymbolLock
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyboard::Location;
+
+    fn press(modifiers: Modifiers, key: Key, code: Code) -> KeyboardEvent {
+        KeyboardEvent {
+            state: KeyState::Down,
+            key,
+            location: Location::Standard,
+            code,
+            modifiers,
+            is_composing: false,
+            repeat: false,
+        }
+    }
+
+    fn char_press(modifiers: Modifiers, c: &str, code: Code) -> KeyboardEvent {
+        press(modifiers, Key::Character(c.into()), code)
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let opts = ParseOptions::default().with_mod(Modifiers::CONTROL);
+        let s = opts.parse("ctrl+shift+z").unwrap();
+        assert_eq!(s.to_string(), "Ctrl+Shift+Z");
+        assert_eq!(
+            s.chords(),
+            [KeyChord::key(
+                Modifiers::CONTROL | Modifiers::SHIFT,
+                Key::Character("z".into())
+            )]
+        );
+
+        let s = opts.parse("Mod+K  Mod+S").unwrap();
+        assert_eq!(s.chords().len(), 2);
+        assert_eq!(s.to_string(), "Ctrl+K Ctrl+S");
+
+        let s = opts.parse("Alt+ArrowLeft").unwrap();
+        assert_eq!(
+            s.chords(),
+            [KeyChord::key(Modifiers::ALT, NamedKey::ArrowLeft.into())]
+        );
+
+        assert_eq!(opts.parse("Ctrl++").unwrap().to_string(), "Ctrl++");
+        assert_eq!(opts.parse("Space").unwrap().to_string(), "Space");
+
+        assert_eq!(opts.parse(""), Err(ParseShortcutError::Empty));
+        assert_eq!(
+            opts.parse("Ctrl+"),
+            Err(ParseShortcutError::MissingKey("Ctrl+".into()))
+        );
+        assert_eq!(
+            opts.parse("Hyperactive+Z"),
+            Err(ParseShortcutError::UnknownModifier("Hyperactive".into()))
+        );
+        assert_eq!(
+            opts.parse("Ctrl+Banana"),
+            Err(ParseShortcutError::UnknownKey("Banana".into()))
+        );
+    }
+
+    #[test]
+    fn mod_alias() {
+        let mac = ParseOptions::default().with_mod(Modifiers::META);
+        let s = mac.parse("Mod+C").unwrap();
+        assert!(s.matches(&char_press(Modifiers::META, "c", Code::KeyC)));
+        assert!(!s.matches(&char_press(Modifiers::CONTROL, "c", Code::KeyC)));
+
+        let default = ParseOptions::default().mod_modifier;
+        if cfg!(any(target_os = "macos", target_os = "ios")) {
+            assert_eq!(default, Modifiers::META);
+        } else {
+            assert_eq!(default, Modifiers::CONTROL);
+        }
+    }
+
+    #[test]
+    fn match_by_key_and_code() {
+        let by_key: Shortcut = "Ctrl+Shift+Z".parse::<Shortcut>().unwrap();
+        let opts = ParseOptions::default().with_match_by(MatchBy::Code);
+        let by_code = opts.parse("Ctrl+Shift+Z").unwrap();
+        assert_eq!(
+            by_code.chords(),
+            [KeyChord::code(
+                Modifiers::CONTROL | Modifiers::SHIFT,
+                Code::KeyZ
+            )]
+        );
+
+        let mods = Modifiers::CONTROL | Modifiers::SHIFT;
+        // On QWERTZ, the key labelled Z is where QWERTY has Y.
+        let qwertz = char_press(mods, "Z", Code::KeyY);
+        assert!(by_key.matches(&qwertz));
+        assert!(!by_code.matches(&qwertz));
+
+        // Modifiers must match exactly, apart from locks.
+        assert!(!by_key.matches(&char_press(Modifiers::CONTROL, "z", Code::KeyZ)));
+        assert!(by_key.matches(&char_press(mods | Modifiers::CAPS_LOCK, "Z", Code::KeyZ)));
+
+        // Releases never match.
+        let mut release = char_press(mods, "Z", Code::KeyZ);
+        release.state = KeyState::Up;
+        assert!(!by_key.matches(&release));
+
+        assert_eq!(
+            opts.parse("Alt+1").unwrap().chords()[0].key,
+            ChordKey::Code(Code::Digit1)
+        );
+    }
+
+    #[test]
+    fn table_conflicts() {
+        let mut table = ShortcutTable::new();
+        table.insert("Ctrl+Z".parse().unwrap(), 1).unwrap();
+        table.insert("Ctrl+K Ctrl+S".parse().unwrap(), 2).unwrap();
+        table.insert("Ctrl+K Ctrl+T".parse().unwrap(), 3).unwrap();
+
+        let err = table.insert("ctrl+z".parse().unwrap(), 4).unwrap_err();
+        assert_eq!(err.kind, ConflictKind::Same);
+
+        let err = table
+            .insert("Ctrl+Z Ctrl+Y".parse().unwrap(), 4)
+            .unwrap_err();
+        assert_eq!(err.kind, ConflictKind::ExistingIsPrefix);
+
+        let err = table.insert("Ctrl+K".parse().unwrap(), 4).unwrap_err();
+        assert_eq!(err.kind, ConflictKind::NewIsPrefix);
+        assert_eq!(err.existing.to_string(), "Ctrl+K Ctrl+S");
+
+        // Matching by code is checked against matching by key, on a US layout.
+        let by_code = ParseOptions::default().with_match_by(MatchBy::Code);
+        let err = table
+            .insert(by_code.parse("Ctrl+KeyZ").unwrap(), 4)
+            .unwrap_err();
+        assert_eq!(err.kind, ConflictKind::Same);
+        let err = table
+            .insert(by_code.parse("Ctrl+K").unwrap(), 4)
+            .unwrap_err();
+        assert_eq!(err.kind, ConflictKind::NewIsPrefix);
+        table.insert(by_code.parse("Ctrl+Y").unwrap(), 4).unwrap();
+        let err = table.insert("Ctrl+y".parse().unwrap(), 5).unwrap_err();
+        assert_eq!(err.kind, ConflictKind::Same);
+        let err = table
+            .insert(by_code.parse("Ctrl+Z Ctrl+Y").unwrap(), 5)
+            .unwrap_err();
+        assert_eq!(err.kind, ConflictKind::ExistingIsPrefix);
+
+        assert_eq!(table.len(), 4);
+    }
+
+    #[test]
+    fn table_sequences() {
+        let mut table = ShortcutTable::new();
+        table.insert("Ctrl+Z".parse().unwrap(), "undo").unwrap();
+        table
+            .insert("Ctrl+K Ctrl+S".parse().unwrap(), "save all")
+            .unwrap();
+
+        let ctrl = Modifiers::CONTROL;
+        assert_eq!(
+            table.process_keyboard_event(&press(ctrl, NamedKey::Control.into(), Code::ControlLeft)),
+            None
+        );
+        assert_eq!(
+            table.process_keyboard_event(&char_press(ctrl, "k", Code::KeyK)),
+            None
+        );
+        assert!(table.is_pending());
+        // Modifier presses don't interrupt a sequence.
+        assert_eq!(
+            table.process_keyboard_event(&press(ctrl, NamedKey::Control.into(), Code::ControlLeft)),
+            None
+        );
+        assert_eq!(
+            table.process_keyboard_event(&char_press(ctrl, "s", Code::KeyS)),
+            Some(&"save all")
+        );
+        assert!(!table.is_pending());
+
+        // A press that breaks a sequence is matched on its own.
+        table.process_keyboard_event(&char_press(ctrl, "k", Code::KeyK));
+        assert_eq!(
+            table.process_keyboard_event(&char_press(ctrl, "z", Code::KeyZ)),
+            Some(&"undo")
+        );
+        assert!(!table.is_pending());
+
+        assert_eq!(
+            table.process_keyboard_event(&char_press(Modifiers::empty(), "z", Code::KeyZ)),
+            None
+        );
+    }
+
+    #[test]
+    fn table_ignores_composition() {
+        let mut table = ShortcutTable::new();
+        table.insert("Ctrl+Z".parse().unwrap(), "undo").unwrap();
+        table
+            .insert("Ctrl+K Ctrl+S".parse().unwrap(), "save all")
+            .unwrap();
+
+        let ctrl = Modifiers::CONTROL;
+        let composing = |c, code| KeyboardEvent {
+            is_composing: true,
+            ..char_press(ctrl, c, code)
+        };
+        assert_eq!(
+            table.process_keyboard_event(&composing("z", Code::KeyZ)),
+            None
+        );
+        assert_eq!(
+            table.process_keyboard_event(&composing("k", Code::KeyK)),
+            None
+        );
+        assert!(!table.is_pending());
+
+        // Composition does not interrupt a sequence either.
+        table.process_keyboard_event(&char_press(ctrl, "k", Code::KeyK));
+        table.process_keyboard_event(&composing("z", Code::KeyZ));
+        assert_eq!(
+            table.process_keyboard_event(&char_press(ctrl, "s", Code::KeyS)),
+            Some(&"save all")
+        );
+    }
+}
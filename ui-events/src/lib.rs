@@ -13,6 +13,8 @@
 //! - Pointer events: button down/up, move, enter/leave, scroll, gestures
 //! - Rich pointer state: position, pressure, tilt, contact size, modifiers
 //! - Keyboard types re-exported from [`keyboard-types`]
+//! - Keyboard shortcut parsing and matching, in [`keyboard::shortcut`]
+//! - Text composition (IME) events, in [`text_input`]
 //! - A stable vocabulary you can adapt from windowing backends
 //!
 //! This crate is intentionally focused on data structures — it does not open
@@ -29,7 +31,8 @@
 //! - Use [`PointerState::logical_position`](pointer::PointerState::logical_position)
 //!   to obtain logical coordinates using a scale factor.
 //! - Scroll deltas are expressed via [`ScrollDelta`]; see its docs for details
-//!   on page/line/pixel semantics.
+//!   on page/line/pixel semantics. [`ScrollPhase`] and [`ScrollSource`] tell
+//!   where a scroll gesture begins and ends, and whether it came from a wheel or touchpad.
 //!
 //! ## Primary pointer
 //!
@@ -41,7 +44,11 @@
 //! ## Feature flags
 //!
 //! - `std` (default): Use the Rust standard library.
+//! - `libm`: Use `libm` for floating point math in `no_std` environments.
+//!   Either this or `std` is needed for the [`PointerOrientation`](pointer::PointerOrientation)
+//!   tilt conversions.
 //! - `kurbo`: Add convenience methods for converting positions to `kurbo::Point`.
+//! - `serde`: Implement `Serialize` and `Deserialize` for the event and state types.
 //!
 //! ## Examples
 //!
@@ -122,9 +129,18 @@
 // END LINEBENDER LINT SET
 #![no_std]
 
+// `libm` is only used when `std` is unavailable.
+#[cfg(all(feature = "std", feature = "libm"))]
+use libm as _;
+
 pub mod keyboard;
 pub mod pointer;
+pub mod text_input;
 
+#[cfg(any(feature = "std", feature = "libm"))]
+mod math;
 mod scroll;
+#[cfg(feature = "serde")]
+mod serde_dpi;
 
-pub use scroll::ScrollDelta;
+pub use scroll::{ScrollDelta, ScrollPhase, ScrollSource};
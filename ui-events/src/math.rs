@@ -0,0 +1,44 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Floating point functions which need either `std` or `libm`.
+
+#[cfg(feature = "std")]
+pub(crate) fn sin(x: f32) -> f32 {
+    x.sin()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn sin(x: f32) -> f32 {
+    libm::sinf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn cos(x: f32) -> f32 {
+    x.cos()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn cos(x: f32) -> f32 {
+    libm::cosf(x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    y.atan2(x)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    libm::atan2f(y, x)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn hypot(x: f32, y: f32) -> f32 {
+    x.hypot(y)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn hypot(x: f32, y: f32) -> f32 {
+    libm::hypotf(x, y)
+}
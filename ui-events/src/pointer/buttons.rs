@@ -9,6 +9,7 @@
 /// in practice, and Windows doesn't support more than 32 mouse buttons
 /// in most APIs, therefore 32 was chosen as the upper limit.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum PointerButton {
     /// Primary button, commonly the left mouse button, touch contact, pen contact.
@@ -240,6 +241,51 @@ impl From<PointerButton> for PointerButtons {
     }
 }
 
+/// `PointerButtons` is serialized as a sequence of [`PointerButton`] names,
+/// rather than as the raw bitmask, so that it stays readable in traces.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PointerButtons {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.count() as usize))?;
+        for button in NONZERO_VARIANTS {
+            if self.contains(button) {
+                seq.serialize_element(&button)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PointerButtons {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ButtonsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ButtonsVisitor {
+            type Value = PointerButtons;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a sequence of pointer buttons")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut buttons = PointerButtons::new();
+                while let Some(button) = seq.next_element::<PointerButton>()? {
+                    buttons.insert(button);
+                }
+                Ok(buttons)
+            }
+        }
+
+        deserializer.deserialize_seq(ButtonsVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     /// `PointerButtons` debug formatting behavior.
@@ -291,6 +337,26 @@ mod tests {
         );
     }
 
+    /// `PointerButtons` serializes as a list of button names.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_button_names() {
+        use crate::pointer::{PointerButton, PointerButtons};
+
+        let buttons = PointerButton::Primary | PointerButton::X2 | PointerButton::B32;
+        let json = serde_json::to_string(&buttons).unwrap();
+        assert_eq!(json, r#"["Primary","X2","B32"]"#);
+        assert_eq!(
+            serde_json::from_str::<PointerButtons>(&json).unwrap(),
+            buttons
+        );
+        assert_eq!(
+            serde_json::to_string(&PointerButtons::default()).unwrap(),
+            "[]"
+        );
+        assert!(serde_json::from_str::<PointerButtons>(r#"["Left"]"#).is_err());
+    }
+
     /// Verify `PointerButton` is same size as `Option<PointerButton>`.
     #[test]
     fn option_niche_opt() {
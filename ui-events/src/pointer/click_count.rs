@@ -0,0 +1,403 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Backend-independent click and tap counting.
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use super::{PointerEvent, PointerId, PointerType, PointerUpdate};
+
+/// How close, and how soon, a press must follow the previous one to extend its count.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClickThreshold {
+    /// Maximum distance from the previous press, in logical pixels.
+    pub slop: f64,
+    /// Maximum time since the previous release, in nanoseconds.
+    pub timeout: u64,
+}
+
+/// Per-[`PointerType`] configuration for a [`ClickCounter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClickCountConfig {
+    /// Threshold for [`PointerType::Mouse`] and [`PointerType::Unknown`].
+    pub mouse: ClickThreshold,
+    /// Threshold for [`PointerType::Pen`].
+    pub pen: ClickThreshold,
+    /// Threshold for [`PointerType::Touch`].
+    pub touch: ClickThreshold,
+}
+
+impl Default for ClickCountConfig {
+    fn default() -> Self {
+        // The slop values were originally tested against a box rather than a
+        // circle, so they are scaled up to the circle enclosing that box.
+        const BOX_TO_CIRCLE: f64 = core::f64::consts::SQRT_2;
+        const TIMEOUT: u64 = 500_000_000;
+        Self {
+            // This is slightly more forgiving than the default on Windows for mice.
+            mouse: ClickThreshold {
+                slop: 2.0 * BOX_TO_CIRCLE,
+                timeout: TIMEOUT,
+            },
+            pen: ClickThreshold {
+                slop: 6.0 * BOX_TO_CIRCLE,
+                timeout: TIMEOUT,
+            },
+            // This is on the low side of double tap slop, validated
+            // experimentally to work on a few touchscreen laptops.
+            touch: ClickThreshold {
+                slop: 12.0 * BOX_TO_CIRCLE,
+                timeout: TIMEOUT,
+            },
+        }
+    }
+}
+
+impl ClickCountConfig {
+    /// The threshold that applies to `pointer_type`.
+    pub fn threshold(&self, pointer_type: PointerType) -> ClickThreshold {
+        match pointer_type {
+            PointerType::Pen => self.pen,
+            PointerType::Touch => self.touch,
+            PointerType::Mouse | PointerType::Unknown => self.mouse,
+        }
+    }
+
+    /// Set the threshold for `pointer_type` (builder style).
+    ///
+    /// [`PointerType::Unknown`] shares its threshold with [`PointerType::Mouse`].
+    pub fn with_threshold(mut self, pointer_type: PointerType, threshold: ClickThreshold) -> Self {
+        match pointer_type {
+            PointerType::Pen => self.pen = threshold,
+            PointerType::Touch => self.touch = threshold,
+            PointerType::Mouse | PointerType::Unknown => self.mouse = threshold,
+        }
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TapState {
+    /// Pointer ID used to attach tap counts to [`PointerEvent::Move`].
+    pointer_id: Option<PointerId>,
+    /// Type of the pointer that last pressed.
+    pointer_type: PointerType,
+    /// Nanosecond timestamp when the tap went Down.
+    down_time: u64,
+    /// Nanosecond timestamp when the tap went Up.
+    ///
+    /// Resets to `down_time` when tap goes Down.
+    up_time: u64,
+    /// The local tap count as of the last Down phase.
+    count: u8,
+    /// x coordinate.
+    x: f64,
+    /// y coordinate.
+    y: f64,
+}
+
+/// Computes [`PointerState::count`] for a stream of [`PointerEvent`]s.
+///
+/// A press counts as a repeat of the previous one if it comes from the same kind of
+/// pointer, lands within [`ClickThreshold::slop`] of it, and starts within
+/// [`ClickThreshold::timeout`] of its release.
+/// Moves while pressed, and the matching release, carry the count of the press.
+/// [`PointerEvent::Cancel`] and [`PointerEvent::Leave`] reset the count for that pointer.
+///
+/// Backends that do not get click counts from the platform should pass every event
+/// through [`ClickCounter::attach_count`] before handing it out.
+///
+/// [`PointerState::count`]: super::PointerState::count
+#[derive(Clone, Debug, Default)]
+pub struct ClickCounter {
+    /// Configuration.
+    config: ClickCountConfig,
+    /// Recent taps.
+    taps: Vec<TapState>,
+}
+
+impl ClickCounter {
+    /// Create a click counter with the given configuration.
+    pub fn new(config: ClickCountConfig) -> Self {
+        Self {
+            config,
+            taps: Vec::new(),
+        }
+    }
+
+    /// The configuration.
+    pub fn config(&self) -> &ClickCountConfig {
+        &self.config
+    }
+
+    /// Forget all previous clicks.
+    pub fn reset(&mut self) {
+        self.taps.clear();
+    }
+
+    /// Enhance a [`PointerEvent`] with a `count`.
+    ///
+    /// Distances are converted to physical pixels with the state's `scale_factor`.
+    pub fn attach_count(&mut self, e: PointerEvent) -> PointerEvent {
+        match e {
+            PointerEvent::Down(mut event) => {
+                let pointer_id = event.pointer.pointer_id;
+                let pointer_type = event.pointer.pointer_type;
+                let position = event.state.position;
+                let time = event.state.time;
+
+                let threshold = self.config.threshold(pointer_type);
+                let slop = threshold.slop * event.state.scale_factor;
+
+                if let Some(tap) = self.taps.iter_mut().find(|tap| {
+                    let dx = tap.x - position.x;
+                    let dy = tap.y - position.y;
+                    tap.pointer_type == pointer_type
+                        && dx * dx + dy * dy < slop * slop
+                        && tap.up_time.saturating_add(threshold.timeout) > time
+                }) {
+                    let count = tap.count.saturating_add(1);
+                    event.state.count = count;
+                    tap.count = count;
+                    tap.pointer_id = pointer_id;
+                    tap.down_time = time;
+                    tap.up_time = time;
+                    tap.x = position.x;
+                    tap.y = position.y;
+                } else {
+                    let s = TapState {
+                        pointer_id,
+                        pointer_type,
+                        down_time: time,
+                        up_time: time,
+                        count: 1,
+                        x: position.x,
+                        y: position.y,
+                    };
+                    if let Some(t) = self
+                        .taps
+                        .iter_mut()
+                        .find(|state| state.pointer_id == pointer_id)
+                    {
+                        *t = s;
+                    } else {
+                        self.taps.push(s);
+                    }
+                    event.state.count = 1;
+                };
+                self.clear_expired(time);
+                PointerEvent::Down(event)
+            }
+            PointerEvent::Up(mut event) => {
+                let p_id = event.pointer.pointer_id;
+                if let Some(tap) = self.taps.iter_mut().find(|state| state.pointer_id == p_id) {
+                    tap.up_time = event.state.time;
+                    event.state.count = tap.count;
+                }
+                PointerEvent::Up(event)
+            }
+            PointerEvent::Move(PointerUpdate {
+                pointer,
+                mut current,
+                mut coalesced,
+                mut predicted,
+            }) => {
+                if let Some(count) = self
+                    .taps
+                    .iter()
+                    .find(|tap| {
+                        tap.pointer_id == pointer.pointer_id && tap.down_time == tap.up_time
+                    })
+                    .map(|tap| tap.count)
+                {
+                    current.count = count;
+                    for event in coalesced.iter_mut() {
+                        event.count = count;
+                    }
+                    for event in predicted.iter_mut() {
+                        event.count = count;
+                    }
+                }
+                PointerEvent::Move(PointerUpdate {
+                    pointer,
+                    current,
+                    coalesced,
+                    predicted,
+                })
+            }
+            PointerEvent::Cancel(p) => {
+                self.taps.retain(|tap| tap.pointer_id != p.pointer_id);
+                PointerEvent::Cancel(p)
+            }
+            PointerEvent::Leave(p) => {
+                self.taps.retain(|tap| tap.pointer_id != p.pointer_id);
+                PointerEvent::Leave(p)
+            }
+            e
+            @ (PointerEvent::Enter(..) | PointerEvent::Scroll(..) | PointerEvent::Gesture(..)) => e,
+        }
+    }
+
+    /// Clear expired taps.
+    ///
+    /// `t` is the time of the last received event.
+    fn clear_expired(&mut self, t: u64) {
+        let config = self.config;
+        self.taps.retain(|tap| {
+            tap.down_time == tap.up_time
+                || tap
+                    .up_time
+                    .saturating_add(config.threshold(tap.pointer_type).timeout)
+                    > t
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::{
+        PointerButton, PointerButtonEvent, PointerButtons, PointerInfo, PointerState,
+    };
+    use alloc::vec;
+    use dpi::PhysicalPosition;
+
+    const MS: u64 = 1_000_000;
+
+    fn info(id: u64, pointer_type: PointerType) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type,
+        }
+    }
+
+    fn state(time: u64, x: f64, buttons: PointerButtons) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y: 0.0 },
+            buttons,
+            ..Default::default()
+        }
+    }
+
+    fn down(pointer: PointerInfo, time: u64, x: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer,
+            state: state(time, x, PointerButton::Primary.into()),
+        })
+    }
+
+    fn up(pointer: PointerInfo, time: u64, x: f64) -> PointerEvent {
+        PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer,
+            state: state(time, x, PointerButtons::default()),
+        })
+    }
+
+    fn count(e: &PointerEvent) -> u8 {
+        match e {
+            PointerEvent::Down(e) | PointerEvent::Up(e) => e.state.count,
+            PointerEvent::Move(e) => e.current.count,
+            _ => panic!("event {e:?} has no count"),
+        }
+    }
+
+    /// Press and release `pointer` at `x`, returning the count of the press.
+    fn click(counter: &mut ClickCounter, pointer: PointerInfo, time: u64, x: f64) -> u8 {
+        let c = count(&counter.attach_count(down(pointer, time, x)));
+        assert_eq!(
+            count(&counter.attach_count(up(pointer, time + 10 * MS, x))),
+            c,
+            "release should carry the count of the press"
+        );
+        c
+    }
+
+    #[test]
+    fn triple_click() {
+        let mouse = info(1, PointerType::Mouse);
+        let mut counter = ClickCounter::default();
+        assert_eq!(click(&mut counter, mouse, 0, 10.0), 1);
+        assert_eq!(click(&mut counter, mouse, 200 * MS, 11.0), 2);
+
+        let moved = counter.attach_count(PointerEvent::Move(PointerUpdate {
+            pointer: mouse,
+            current: state(300 * MS, 11.0, PointerButtons::default()),
+            coalesced: vec![],
+            predicted: vec![],
+        }));
+        assert_eq!(count(&moved), 0, "hover moves carry no count");
+
+        assert_eq!(click(&mut counter, mouse, 400 * MS, 10.0), 3);
+
+        // Too slow for a fourth click.
+        assert_eq!(click(&mut counter, mouse, 1_000 * MS, 10.0), 1);
+    }
+
+    #[test]
+    fn moves_while_pressed_carry_count() {
+        let mouse = info(1, PointerType::Mouse);
+        let mut counter = ClickCounter::default();
+        click(&mut counter, mouse, 0, 0.0);
+        counter.attach_count(down(mouse, 100 * MS, 0.0));
+        let moved = counter.attach_count(PointerEvent::Move(PointerUpdate {
+            pointer: mouse,
+            current: state(110 * MS, 1.0, PointerButton::Primary.into()),
+            coalesced: vec![state(105 * MS, 0.5, PointerButton::Primary.into())],
+            predicted: vec![],
+        }));
+        let PointerEvent::Move(update) = moved else {
+            unreachable!()
+        };
+        assert_eq!(update.current.count, 2);
+        assert_eq!(update.coalesced[0].count, 2);
+    }
+
+    #[test]
+    fn pen_vs_touch_slop() {
+        let pen = info(2, PointerType::Pen);
+        let touch = info(3, PointerType::Touch);
+        let mut counter = ClickCounter::default();
+
+        // 10px is outside the default pen slop…
+        click(&mut counter, pen, 0, 0.0);
+        assert_eq!(click(&mut counter, pen, 100 * MS, 10.0), 1);
+
+        // …but inside the default touch slop.
+        click(&mut counter, touch, 1_000 * MS, 0.0);
+        assert_eq!(click(&mut counter, touch, 1_100 * MS, 10.0), 2);
+
+        // A pen press does not continue a touch sequence, even in the same place.
+        assert_eq!(click(&mut counter, pen, 1_200 * MS, 10.0), 1);
+
+        // Slop is configurable per pointer type, in logical pixels.
+        let mut counter = ClickCounter::new(ClickCountConfig::default().with_threshold(
+            PointerType::Pen,
+            ClickThreshold {
+                slop: 20.0,
+                timeout: 500 * MS,
+            },
+        ));
+        click(&mut counter, pen, 0, 0.0);
+        assert_eq!(click(&mut counter, pen, 100 * MS, 10.0), 2);
+    }
+
+    #[test]
+    fn cancel_and_leave_reset() {
+        let touch = info(3, PointerType::Touch);
+        let mut counter = ClickCounter::default();
+        click(&mut counter, touch, 0, 0.0);
+        counter.attach_count(PointerEvent::Cancel(touch));
+        assert_eq!(click(&mut counter, touch, 100 * MS, 0.0), 1);
+
+        let mouse = info(1, PointerType::Mouse);
+        click(&mut counter, mouse, 1_000 * MS, 0.0);
+        assert_eq!(click(&mut counter, mouse, 1_100 * MS, 0.0), 2);
+        counter.attach_count(PointerEvent::Leave(mouse));
+        assert_eq!(click(&mut counter, mouse, 1_200 * MS, 0.0), 1);
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use super::{PointerEvent, PointerUpdate};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Folds runs of [`PointerEvent::Move`] into single updates with [`PointerUpdate::coalesced`] states.
+///
+/// Moves are held back until something else happens, and consecutive moves of
+/// the same [`PointerInfo`] are folded into the latest one, with the earlier
+/// states appended to its `coalesced` states, and only the latest `predicted` states kept.
+/// Moves of different pointers are held side by side, so interleaved touches
+/// are coalesced too.
+///
+/// Any other pointer event, such as a button transition, a [`PointerEvent::Cancel`]
+/// or a [`PointerEvent::Leave`], first releases all held moves, in the order they
+/// started, so that it is seen after the motion that preceded it.
+/// A move whose buttons differ from the held move of its pointer does the same.
+///
+/// This matches how browsers coalesce `pointermove`, for backends without
+/// native coalescing, such as `ui-events-winit`.
+/// Call [`MoveCoalescer::flush`] before handling events of other kinds, such as
+/// keyboard events, and once all of a frame's input has been received.
+///
+/// [`PointerInfo`]: super::PointerInfo
+#[derive(Clone, Debug, Default)]
+pub struct MoveCoalescer {
+    /// Held moves, at most one per pointer, in the order they started.
+    pending: Vec<PointerUpdate>,
+}
+
+impl MoveCoalescer {
+    /// Create an empty move coalescer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return `true` if no moves are held.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Add a [`PointerEvent`], returning the events ready to be handled, in order.
+    pub fn push(&mut self, event: PointerEvent) -> Vec<PointerEvent> {
+        let PointerEvent::Move(update) = event else {
+            let mut out = self.flush();
+            out.push(event);
+            return out;
+        };
+
+        match self
+            .pending
+            .iter_mut()
+            .find(|p| p.pointer.pointer_id == update.pointer.pointer_id)
+        {
+            Some(pending)
+                if pending.pointer == update.pointer
+                    && pending.current.buttons == update.current.buttons =>
+            {
+                let previous = core::mem::replace(&mut pending.current, update.current);
+                pending.coalesced.push(previous);
+                pending.coalesced.extend(update.coalesced);
+                pending.predicted = update.predicted;
+                Vec::new()
+            }
+            Some(_) => {
+                let out = self.flush();
+                self.pending.push(update);
+                out
+            }
+            None => {
+                self.pending.push(update);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Release all held moves, in the order they started.
+    pub fn flush(&mut self) -> Vec<PointerEvent> {
+        self.pending.drain(..).map(PointerEvent::Move).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::{
+        PointerButton, PointerButtonEvent, PointerId, PointerInfo, PointerState, PointerType,
+    };
+    use alloc::vec;
+
+    fn info(id: u64) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type: PointerType::Touch,
+        }
+    }
+
+    fn state(time: u64) -> PointerState {
+        PointerState {
+            time,
+            ..Default::default()
+        }
+    }
+
+    fn move_at(id: u64, time: u64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer: info(id),
+            current: state(time),
+            coalesced: vec![],
+            predicted: vec![state(time + 5)],
+        })
+    }
+
+    /// Pointer ID, current time, coalesced times and predicted times of a move.
+    fn times(e: &PointerEvent) -> (u64, u64, Vec<u64>, Vec<u64>) {
+        let PointerEvent::Move(update) = e else {
+            panic!("expected a move, got {e:?}");
+        };
+        (
+            update.pointer.pointer_id.unwrap().get_inner().get(),
+            update.current.time,
+            update.coalesced.iter().map(|s| s.time).collect(),
+            update.predicted.iter().map(|s| s.time).collect(),
+        )
+    }
+
+    #[test]
+    fn folds_moves_per_pointer() {
+        let mut c = MoveCoalescer::new();
+        assert!(c.push(move_at(1, 1)).is_empty());
+        assert!(c.push(move_at(2, 2)).is_empty());
+        assert!(c.push(move_at(1, 3)).is_empty());
+        assert!(c.push(move_at(1, 4)).is_empty());
+        assert!(!c.is_empty());
+
+        let out = c.flush();
+        assert_eq!(out.len(), 2);
+        assert_eq!(times(&out[0]), (1, 4, vec![1, 3], vec![9]));
+        assert_eq!(times(&out[1]), (2, 2, vec![], vec![7]));
+        assert!(c.is_empty());
+        assert!(c.flush().is_empty());
+    }
+
+    #[test]
+    fn other_events_release_moves_first() {
+        let mut c = MoveCoalescer::new();
+        c.push(move_at(1, 1));
+        c.push(move_at(2, 2));
+        c.push(move_at(1, 3));
+
+        let up = PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(2),
+            state: state(4),
+        });
+        let out = c.push(up);
+        assert_eq!(out.len(), 3);
+        assert_eq!(times(&out[0]), (1, 3, vec![1], vec![8]));
+        assert_eq!(times(&out[1]), (2, 2, vec![], vec![7]));
+        assert!(matches!(out[2], PointerEvent::Up(..)));
+        assert!(c.is_empty());
+
+        c.push(move_at(1, 5));
+        let out = c.push(PointerEvent::Cancel(info(1)));
+        assert_eq!(out.len(), 2);
+        assert!(matches!(out[1], PointerEvent::Cancel(..)));
+    }
+
+    #[test]
+    fn button_changes_release_moves() {
+        let mut c = MoveCoalescer::new();
+        c.push(move_at(1, 1));
+        let PointerEvent::Move(mut update) = move_at(1, 2) else {
+            unreachable!();
+        };
+        update.current.buttons = PointerButton::Primary.into();
+        let out = c.push(PointerEvent::Move(update));
+        assert_eq!(out.len(), 1);
+        assert_eq!(times(&out[0]), (1, 1, vec![], vec![6]));
+        assert_eq!(times(&c.flush()[0]), (1, 2, vec![], vec![7]));
+    }
+}
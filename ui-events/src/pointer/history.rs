@@ -0,0 +1,252 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Recent pointer motion, and the velocity and acceleration fitted to it.
+
+extern crate alloc;
+use alloc::collections::VecDeque;
+
+use dpi::PhysicalPosition;
+
+/// A short history of positions of one pointer.
+///
+/// Velocity and acceleration are estimated from it with a least-squares
+/// polynomial fit, like Android's `VelocityTracker`. The history is robust
+/// to the ways real event streams misbehave:
+///
+/// - Samples older than the horizon, measured from the newest sample, are dropped.
+/// - A gap longer than the maximum gap between samples means the pointer
+///   stopped, so older history is discarded.
+/// - A sample with the same timestamp as the previous one replaces it,
+///   and samples that go back in time are ignored.
+///
+/// Times are in nanoseconds, and positions in physical pixels.
+#[derive(Clone, Debug)]
+pub struct MotionHistory {
+    /// How far back from the newest sample samples are kept.
+    horizon: u64,
+    /// Longest time between two samples before older history is discarded.
+    max_gap: u64,
+    /// Maximum number of samples kept.
+    max_samples: usize,
+    /// Recent samples, ordered by strictly increasing time.
+    samples: VecDeque<(u64, PhysicalPosition<f64>)>,
+}
+
+impl Default for MotionHistory {
+    fn default() -> Self {
+        Self {
+            horizon: 100_000_000,
+            max_gap: 40_000_000,
+            max_samples: 20,
+            samples: VecDeque::new(),
+        }
+    }
+}
+
+impl MotionHistory {
+    /// Set the history horizon (builder style).
+    pub fn with_horizon(mut self, horizon: u64) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Set the maximum gap between samples (builder style).
+    pub fn with_max_gap(mut self, max_gap: u64) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Set the maximum number of samples (builder style).
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+
+    /// Number of samples in the history.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether the history has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Forget all samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Add a position sample.
+    pub fn add_sample(&mut self, time: u64, position: PhysicalPosition<f64>) {
+        if let Some(&(last_time, _)) = self.samples.back() {
+            if time < last_time {
+                return;
+            }
+            if time == last_time {
+                self.samples.pop_back();
+            } else if time - last_time > self.max_gap {
+                self.samples.clear();
+            }
+        }
+        self.samples.push_back((time, position));
+        while self.samples.len() > self.max_samples.max(1)
+            || self
+                .samples
+                .front()
+                .is_some_and(|&(t, _)| time - t > self.horizon)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Velocity at the newest sample, in pixels per second, from a straight-line fit.
+    ///
+    /// Returns `None` with fewer than two samples.
+    pub fn velocity(&self) -> Option<PhysicalPosition<f64>> {
+        self.fit(2).map(|(velocity, _)| velocity)
+    }
+
+    /// Velocity and acceleration at the newest sample, in pixels per second
+    /// and per second squared.
+    ///
+    /// The fit is quadratic with at least three samples, and linear with two,
+    /// in which case the acceleration is zero.
+    /// Returns `None` with fewer than two samples.
+    pub fn velocity_and_acceleration(
+        &self,
+    ) -> Option<(PhysicalPosition<f64>, PhysicalPosition<f64>)> {
+        self.fit(self.samples.len())
+    }
+
+    /// Derivatives of a fit of degree 1 when `n` is 2, or 2 otherwise.
+    fn fit(&self, n: usize) -> Option<(PhysicalPosition<f64>, PhysicalPosition<f64>)> {
+        let &(newest, _) = self.samples.back()?;
+        if self.samples.len() < 2 {
+            return None;
+        }
+        // Fit in milliseconds relative to the newest sample, to keep
+        // the sums well conditioned.
+        let points = || {
+            self.samples
+                .iter()
+                .map(move |&(t, p)| (-((newest - t) as f64) * 1e-6, p))
+        };
+        let (vx, ax) = fit(points().map(|(t, p)| (t, p.x)), n);
+        let (vy, ay) = fit(points().map(|(t, p)| (t, p.y)), n);
+        Some((
+            PhysicalPosition::new(vx * 1e3, vy * 1e3),
+            PhysicalPosition::new(ax * 1e6, ay * 1e6),
+        ))
+    }
+}
+
+/// Least-squares fit of `x(t) = a + b t + c t²` to `n >= 2` samples with
+/// distinct times, returning the first and second derivatives at `t = 0`.
+///
+/// Fits a line when `n` is 2, or when the quadratic system is degenerate.
+fn fit(samples: impl Iterator<Item = (f64, f64)>, n: usize) -> (f64, f64) {
+    let mut s = [0_f64; 5];
+    let mut sx = [0_f64; 3];
+    for (t, x) in samples {
+        let mut tk = 1.0;
+        for (k, sk) in s.iter_mut().enumerate() {
+            *sk += tk;
+            if let Some(sxk) = sx.get_mut(k) {
+                *sxk += tk * x;
+            }
+            tk *= t;
+        }
+    }
+
+    if n >= 3 {
+        let det3 = |m: [[f64; 3]; 3]| {
+            m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+        };
+        let m = [[s[0], s[1], s[2]], [s[1], s[2], s[3]], [s[2], s[3], s[4]]];
+        let det = det3(m);
+        if det.abs() > 1e-9 {
+            // Cramer's rule for the linear and quadratic coefficients.
+            let mut mb = m;
+            let mut mc = m;
+            for row in 0..3 {
+                mb[row][1] = sx[row];
+                mc[row][2] = sx[row];
+            }
+            return (det3(mb) / det, 2.0 * det3(mc) / det);
+        }
+    }
+
+    let det = s[0] * s[2] - s[1] * s[1];
+    if det.abs() <= 1e-9 {
+        return (0.0, 0.0);
+    }
+    ((s[0] * sx[1] - s[1] * sx[0]) / det, 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1_000_000;
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "expected {a} ~= {b}");
+    }
+
+    #[test]
+    fn linear_and_quadratic_fits() {
+        // x = 0.5 a t², with a = 2000 px/s² and t in seconds.
+        let mut h = MotionHistory::default();
+        assert_eq!(h.velocity(), None);
+        for i in 0..6_u64 {
+            let secs = (i * 10) as f64 * 1e-3;
+            h.add_sample(
+                i * 10 * MS,
+                PhysicalPosition::new(1_000.0 * secs * secs, -500.0 * secs),
+            );
+        }
+        let (v, a) = h.velocity_and_acceleration().unwrap();
+        // v = a t at t = 50ms.
+        assert_approx(v.x, 100.0);
+        assert_approx(a.x, 2_000.0);
+        assert_approx(v.y, -500.0);
+        assert_approx(a.y, 0.0);
+        // The straight line through the parabola lags behind.
+        assert!(h.velocity().unwrap().x < 100.0);
+        assert_approx(h.velocity().unwrap().y, -500.0);
+    }
+
+    #[test]
+    fn pruning() {
+        let mut h = MotionHistory::default().with_max_samples(3);
+        h.add_sample(0, PhysicalPosition::new(0.0, 0.0));
+        h.add_sample(10 * MS, PhysicalPosition::new(99.0, 0.0));
+        // Replaces the previous sample.
+        h.add_sample(10 * MS, PhysicalPosition::new(10.0, 0.0));
+        // Ignored.
+        h.add_sample(5 * MS, PhysicalPosition::new(-50.0, 0.0));
+        assert_eq!(h.len(), 2);
+        assert_approx(h.velocity().unwrap().x, 1_000.0);
+
+        h.add_sample(20 * MS, PhysicalPosition::new(20.0, 0.0));
+        h.add_sample(30 * MS, PhysicalPosition::new(30.0, 0.0));
+        assert_eq!(h.len(), 3);
+
+        // A gap longer than `max_gap` starts over.
+        h.add_sample(100 * MS, PhysicalPosition::new(30.0, 0.0));
+        assert_eq!(h.len(), 1);
+        assert_eq!(h.velocity(), None);
+
+        let mut h = MotionHistory::default().with_horizon(25 * MS);
+        for i in 0..5_u64 {
+            h.add_sample(i * 10 * MS, PhysicalPosition::new(0.0, 0.0));
+        }
+        assert_eq!(h.len(), 3);
+        h.clear();
+        assert!(h.is_empty());
+    }
+}
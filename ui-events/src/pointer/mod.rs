@@ -12,7 +12,12 @@
 //! - [`PointerId`] and [`PersistentDeviceId`] help correlate states over time.
 //! - [`PointerState`] carries position, pressure, tilt, modifiers and more.
 //! - [`PointerEvent`] is the main event enum: down/up/move/enter/leave/scroll/gesture.
+//! - [`RelativeMotionEvent`] carries raw or locked pointer motion, apart from positions.
 //! - [`PointerInfo::is_primary_pointer`] is a convenience for primary interactions.
+//! - [`ClickCounter`] computes [`PointerState::count`] consistently across backends.
+//! - [`MoveCoalescer`] folds runs of moves into [`PointerUpdate::coalesced`] states.
+//! - [`MotionHistory`] fits velocity and acceleration to recent positions.
+//! - [`MotionPredictor`] fills [`PointerUpdate::predicted`] where the platform does not.
 //!
 //! ## Example: checking for primary pointer and using logical coordinates
 //!
@@ -30,8 +35,16 @@
 //! ```
 
 mod buttons;
+mod click_count;
+mod coalesce;
+mod history;
+mod prediction;
 
 pub use buttons::{PointerButton, PointerButtons};
+pub use click_count::{ClickCountConfig, ClickCounter, ClickThreshold};
+pub use coalesce::MoveCoalescer;
+pub use history::MotionHistory;
+pub use prediction::{MotionPredictor, PredictionConfig, PredictionModel};
 
 extern crate alloc;
 use alloc::vec::Vec;
@@ -41,12 +54,13 @@ use core::num::NonZeroU64;
 use dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
 use keyboard_types::Modifiers;
 
-use crate::ScrollDelta;
+use crate::{ScrollDelta, ScrollPhase, ScrollSource};
 
 /// A unique identifier for the pointer.
 ///
 /// PointerId(1) is reserved for the primary pointer.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerId(NonZeroU64);
 
 impl PointerId {
@@ -76,6 +90,7 @@ impl PointerId {
 ///
 /// PointerId(1) is reserved for the primary pointer.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PersistentDeviceId(NonZeroU64);
 
 impl PersistentDeviceId {
@@ -89,6 +104,7 @@ impl PersistentDeviceId {
 /// The type of device that has generated a pointer event.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum PointerType {
     /// The type of device could not be determined.
@@ -104,6 +120,7 @@ pub enum PointerType {
 
 /// Identifying information about a pointer, stable across states.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerInfo {
     /// Pointer ID.
     ///
@@ -133,6 +150,7 @@ impl PointerInfo {
 
 /// Orientation of a pointer.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerOrientation {
     /// Spherical altitude.
     ///
@@ -140,8 +158,14 @@ pub struct PointerOrientation {
     pub altitude: f32,
     /// Spherical azimuth.
     ///
-    /// 0 is the positive x axis, π/2 is positive y.
+    /// 0 is the positive x axis, π/2 is positive y, in the range `[0, 2π)`
+    /// as with the Pointer Events `azimuthAngle`.
     pub azimuth: f32,
+    /// Rotation of a pen around its own major axis, in radians.
+    ///
+    /// This increases clockwise, in the range `[0, 2π)`.
+    /// It is 0 when the device does not report rotation.
+    pub twist: f32,
 }
 
 impl Default for PointerOrientation {
@@ -149,8 +173,65 @@ impl Default for PointerOrientation {
         Self {
             altitude: core::f32::consts::FRAC_PI_2,
             azimuth: core::f32::consts::FRAC_PI_2,
+            twist: 0.0,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl PointerOrientation {
+    /// Make an orientation from plane angles, in radians.
+    ///
+    /// `tilt_x` is the angle between the Y-Z plane and the plane containing
+    /// both the pen axis and the Y axis, positive toward positive x.
+    /// `tilt_y` is the angle between the X-Z plane and the plane containing
+    /// both the pen axis and the X axis, positive toward positive y.
+    /// Both are in `[-π/2, π/2]`, as with the Pointer Events `tiltX` and `tiltY`
+    /// (which are in degrees).
+    ///
+    /// The azimuth is in `[0, 2π)`, and is π/2 when there is no tilt,
+    /// matching [`Default`]. `twist` is 0.
+    pub fn from_tilt(tilt_x: f32, tilt_y: f32) -> Self {
+        use crate::math::{atan2, cos, hypot, sin};
+
+        // The pen axis is (x, y, z) with x/z = tan(tilt_x) and y/z = tan(tilt_y).
+        // Scaling by cos(tilt_x) cos(tilt_y) avoids the singularities of tan at ±π/2.
+        let x = sin(tilt_x) * cos(tilt_y);
+        let y = cos(tilt_x) * sin(tilt_y);
+        let z = cos(tilt_x) * cos(tilt_y);
+
+        let planar = hypot(x, y);
+        let azimuth = if planar == 0.0 {
+            core::f32::consts::FRAC_PI_2
+        } else {
+            // Map the `(-π, π]` of `atan2` to `[0, 2π)`.
+            let azimuth = atan2(y, x);
+            if azimuth >= 0.0 {
+                azimuth
+            } else {
+                // Rounding can give 2π for tiny negative angles.
+                (azimuth + core::f32::consts::TAU) % core::f32::consts::TAU
+            }
+        };
+
+        Self {
+            altitude: atan2(z, planar),
+            azimuth,
+            twist: 0.0,
         }
     }
+
+    /// Plane angles `(tilt_x, tilt_y)` for this orientation, in radians.
+    ///
+    /// This is the inverse of [`PointerOrientation::from_tilt`].
+    pub fn tilt(self) -> (f32, f32) {
+        use crate::math::{atan2, cos, sin};
+
+        let x = cos(self.altitude) * cos(self.azimuth);
+        let y = cos(self.altitude) * sin(self.azimuth);
+        let z = sin(self.altitude);
+        (atan2(x, z), atan2(y, z))
+    }
 }
 
 /// The size of an input, usually touch.
@@ -161,6 +242,7 @@ pub type ContactGeometry = PhysicalSize<f64>;
 
 /// A single pointer state.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerState {
     /// `u64` nanoseconds real time.
     ///
@@ -169,17 +251,24 @@ pub struct PointerState {
     /// same device.
     pub time: u64,
     /// Position.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_dpi::PhysicalPositionDef")
+    )]
     pub position: PhysicalPosition<f64>,
     /// Pressed buttons.
     pub buttons: PointerButtons,
     /// Modifiers state.
     pub modifiers: Modifiers,
     /// Click or tap count associated with the pointer.
+    ///
+    /// See [`ClickCounter`] for how backends compute this.
     pub count: u8,
     /// The size of an input, usually touch.
     ///
     /// If this is not provided by the underlying API, platform, or device,
     /// then it should be a single pixel.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_dpi::PhysicalSizeDef"))]
     pub contact_geometry: ContactGeometry,
     /// Orientation.
     pub orientation: PointerOrientation,
@@ -251,6 +340,7 @@ impl Default for PointerState {
 
 /// A pointer update, along with coalesced and predicted states.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerUpdate {
     /// Identifying information about pointer.
     pub pointer: PointerInfo,
@@ -260,13 +350,14 @@ pub struct PointerUpdate {
     ///
     /// Coalescing is application-specific.
     /// On the web, the browser does its own coalescing, whereas
-    /// on other platforms you may do your own, or forego it
-    /// altogether, delivering every state.
+    /// on other platforms you may do your own, for example with
+    /// [`MoveCoalescer`], or forego it altogether, delivering every state.
     pub coalesced: Vec<PointerState>,
     /// Predicted states, ordered by `time`.
     ///
     /// Some platforms provide predicted states directly,
-    /// and you may choose to add your own predictor.
+    /// and you may choose to add your own predictor,
+    /// such as [`MotionPredictor`].
     pub predicted: Vec<PointerState>,
 }
 
@@ -280,6 +371,7 @@ impl PointerUpdate {
 
 /// An event representing a [`PointerButton`] that was pressed or released.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerButtonEvent {
     /// The [`PointerButton`] that was pressed.
     pub button: Option<PointerButton>,
@@ -291,17 +383,23 @@ pub struct PointerButtonEvent {
 
 /// An event representing a scroll
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerScrollEvent {
     /// Identity of the pointer.
     pub pointer: PointerInfo,
     /// The delta of the scroll.
     pub delta: ScrollDelta,
+    /// Where this event falls within a scroll gesture.
+    pub phase: ScrollPhase,
+    /// The kind of device that scrolled.
+    pub source: ScrollSource,
     /// The state of the pointer (i.e. position, pressure, etc.).
     pub state: PointerState,
 }
 
 /// A touchpad gesture for pointer.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PointerGesture {
     /// Pinch delta.
     ///
@@ -320,6 +418,7 @@ pub enum PointerGesture {
 
 /// An event representing a gesture
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointerGestureEvent {
     /// Identity of the pointer.
     pub pointer: PointerInfo,
@@ -329,6 +428,46 @@ pub struct PointerGestureEvent {
     pub state: PointerState,
 }
 
+/// Relative motion of a pointing device.
+///
+/// Deltas are in a Y-down coordinate system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MotionDelta {
+    /// Raw delta, in the units of the device.
+    ///
+    /// This is motion as reported by the device, such as mouse counts, before
+    /// any pointer acceleration. It has no fixed relation to pixels.
+    RawDelta(f64, f64),
+    /// Pixel delta.
+    ///
+    /// This is motion after the platform's pointer acceleration, as it would
+    /// have moved the cursor.
+    PixelDelta(
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_dpi::PhysicalPositionDef")
+        )]
+        PhysicalPosition<f64>,
+    ),
+}
+
+/// An event representing relative motion of a pointing device.
+///
+/// Unlike [`PointerEvent::Move`], this is not tied to a position on a surface,
+/// and it keeps coming when the cursor is held in place, for example by pointer lock,
+/// or against the edge of the screen. This makes it suited to camera controls.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeMotionEvent {
+    /// Identity of the pointer.
+    pub pointer: PointerInfo,
+    /// The motion since the previous event.
+    pub delta: MotionDelta,
+    /// The state of the pointer (i.e. position, pressure, etc.).
+    pub state: PointerState,
+}
+
 /// A standard `PointerEvent`.
 ///
 /// This is intentionally limited to standard pointer events,
@@ -336,6 +475,7 @@ pub struct PointerGestureEvent {
 /// support more event types will use this as a base and add
 /// what they need in a conversion.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PointerEvent {
     /// A [`PointerButton`] was pressed.
     Down(PointerButtonEvent),
@@ -376,3 +516,72 @@ impl PointerEvent {
         }
     }
 }
+
+#[cfg(all(test, any(feature = "std", feature = "libm")))]
+mod tests {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_6, PI};
+
+    fn assert_approx(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "expected {a} ~= {b}");
+    }
+
+    #[test]
+    fn untilted_pen_is_perpendicular() {
+        let o = PointerOrientation::from_tilt(0.0, 0.0);
+        assert_eq!(o, PointerOrientation::default());
+        let (tx, ty) = o.tilt();
+        assert_approx(tx, 0.0);
+        assert_approx(ty, 0.0);
+    }
+
+    #[test]
+    fn tilt_along_axes() {
+        let o = PointerOrientation::from_tilt(FRAC_PI_6, 0.0);
+        assert_approx(o.altitude, FRAC_PI_2 - FRAC_PI_6);
+        assert_approx(o.azimuth, 0.0);
+
+        let o = PointerOrientation::from_tilt(0.0, -FRAC_PI_6);
+        assert_approx(o.altitude, FRAC_PI_2 - FRAC_PI_6);
+        assert_approx(o.azimuth, 3.0 * FRAC_PI_2);
+
+        let o = PointerOrientation::from_tilt(-FRAC_PI_2, 0.0);
+        assert_approx(o.altitude, 0.0);
+        assert_approx(o.azimuth, PI);
+    }
+
+    #[test]
+    fn negative_tilt_azimuth_is_positive() {
+        let o = PointerOrientation::from_tilt(-FRAC_PI_6, 0.0);
+        assert_approx(o.azimuth, PI);
+
+        let o = PointerOrientation::from_tilt(-FRAC_PI_4, -FRAC_PI_4);
+        assert_approx(o.azimuth, 5.0 * FRAC_PI_4);
+
+        let o = PointerOrientation::from_tilt(FRAC_PI_4, -FRAC_PI_4);
+        assert_approx(o.azimuth, 7.0 * FRAC_PI_4);
+
+        for (tx, ty) in [(0.5, -1e-9), (1e-7, -1e-7), (-1.2, 0.3), (0.3, -1.2)] {
+            let o = PointerOrientation::from_tilt(tx, ty);
+            assert!(
+                (0.0..core::f32::consts::TAU).contains(&o.azimuth),
+                "azimuth {} out of range",
+                o.azimuth
+            );
+        }
+    }
+
+    #[test]
+    fn tilt_round_trips() {
+        for (tx, ty) in [
+            (FRAC_PI_6, FRAC_PI_4),
+            (-FRAC_PI_4, FRAC_PI_6),
+            (0.3, -1.2),
+            (-1.0, -0.5),
+        ] {
+            let (rx, ry) = PointerOrientation::from_tilt(tx, ty).tilt();
+            assert_approx(rx, tx);
+            assert_approx(ry, ty);
+        }
+    }
+}
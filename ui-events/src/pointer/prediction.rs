@@ -0,0 +1,385 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Backend-independent motion prediction.
+
+use super::{MotionHistory, PointerEvent, PointerId, PointerState, PointerType, PointerUpdate};
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use dpi::PhysicalPosition;
+
+/// Most samples kept per pointer.
+const MAX_SAMPLES: usize = 20;
+
+/// How a [`MotionPredictor`] extrapolates recent motion.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PredictionModel {
+    /// Continue at the velocity fitted to recent samples.
+    ///
+    /// This is the steadier choice, and overshoots less when the pointer stops.
+    #[default]
+    Linear,
+    /// Continue with the velocity and acceleration fitted to recent samples.
+    ///
+    /// This follows curves more closely, but is more sensitive to noise.
+    /// It behaves like [`PredictionModel::Linear`] with fewer than three samples.
+    Quadratic,
+}
+
+/// Configuration for a [`MotionPredictor`].
+///
+/// Times are in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PredictionConfig {
+    /// How far past [`PointerUpdate::current`] to predict.
+    ///
+    /// This is usually about the latency between input and display.
+    pub look_ahead: u64,
+    /// Time between predicted states.
+    ///
+    /// If this is zero, a single state is predicted at `look_ahead`.
+    pub interval: u64,
+    /// How far back from the newest sample samples are considered.
+    pub horizon: u64,
+    /// Longest time between two samples before the pointer is considered
+    /// to have stopped, discarding the older history.
+    pub max_gap: u64,
+    /// How motion is extrapolated.
+    pub model: PredictionModel,
+}
+
+impl Default for PredictionConfig {
+    fn default() -> Self {
+        Self {
+            look_ahead: 16_000_000,
+            interval: 8_000_000,
+            horizon: 50_000_000,
+            max_gap: 40_000_000,
+            model: PredictionModel::Linear,
+        }
+    }
+}
+
+impl PredictionConfig {
+    /// Set the look-ahead (builder style).
+    pub fn with_look_ahead(mut self, look_ahead: u64) -> Self {
+        self.look_ahead = look_ahead;
+        self
+    }
+
+    /// Set the interval between predicted states (builder style).
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the history horizon (builder style).
+    pub fn with_horizon(mut self, horizon: u64) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Set the maximum gap between samples (builder style).
+    pub fn with_max_gap(mut self, max_gap: u64) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Set the prediction model (builder style).
+    pub fn with_model(mut self, model: PredictionModel) -> Self {
+        self.model = model;
+        self
+    }
+}
+
+/// Fills [`PointerUpdate::predicted`] by extrapolating recent motion.
+///
+/// Every [`PointerEvent::Move`], [`PointerEvent::Down`] and [`PointerEvent::Up`]
+/// passed through [`MotionPredictor::attach_predictions`] is recorded per pointer,
+/// including [`PointerUpdate::coalesced`] states.
+/// Moves without predicted states then get states up to
+/// [`PredictionConfig::look_ahead`] past [`PointerUpdate::current`], fitted by
+/// least squares to the samples within [`PredictionConfig::horizon`].
+/// Predicted states are copies of the current state, with extrapolated `position`
+/// and `time`.
+///
+/// Predictions are only made after two samples, and [`PointerEvent::Cancel`],
+/// [`PointerEvent::Leave`], and lifting a touch, discard the pointer's history.
+/// Moves that already carry platform predictions are recorded but left unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct MotionPredictor {
+    /// Configuration.
+    config: PredictionConfig,
+    /// Recent motion of each pointer.
+    histories: Vec<(Option<PointerId>, MotionHistory)>,
+}
+
+impl MotionPredictor {
+    /// Create a motion predictor with the given configuration.
+    pub fn new(config: PredictionConfig) -> Self {
+        Self {
+            config,
+            histories: Vec::new(),
+        }
+    }
+
+    /// The configuration.
+    pub fn config(&self) -> &PredictionConfig {
+        &self.config
+    }
+
+    /// Forget all previous motion.
+    pub fn reset(&mut self) {
+        self.histories.clear();
+    }
+
+    /// Enhance a [`PointerEvent`] with `predicted` states.
+    pub fn attach_predictions(&mut self, e: PointerEvent) -> PointerEvent {
+        match e {
+            PointerEvent::Move(mut update) => {
+                for state in update.coalesced.iter().chain([&update.current]) {
+                    self.record(update.pointer.pointer_id, state);
+                }
+                if update.predicted.is_empty() {
+                    update.predicted = self.predict(&update);
+                }
+                PointerEvent::Move(update)
+            }
+            PointerEvent::Down(event) => {
+                self.record(event.pointer.pointer_id, &event.state);
+                PointerEvent::Down(event)
+            }
+            PointerEvent::Up(event) => {
+                if event.pointer.pointer_type == PointerType::Touch {
+                    self.remove(event.pointer.pointer_id);
+                } else {
+                    self.record(event.pointer.pointer_id, &event.state);
+                }
+                PointerEvent::Up(event)
+            }
+            PointerEvent::Cancel(p) => {
+                self.remove(p.pointer_id);
+                PointerEvent::Cancel(p)
+            }
+            PointerEvent::Leave(p) => {
+                self.remove(p.pointer_id);
+                PointerEvent::Leave(p)
+            }
+            e
+            @ (PointerEvent::Enter(..) | PointerEvent::Scroll(..) | PointerEvent::Gesture(..)) => e,
+        }
+    }
+
+    /// Predicted states following `update`, whose motion has been recorded.
+    fn predict(&self, update: &PointerUpdate) -> Vec<PointerState> {
+        let current = &update.current;
+        let Some((velocity, acceleration)) = self
+            .histories
+            .iter()
+            .find(|(id, _)| *id == update.pointer.pointer_id)
+            .and_then(|(_, h)| match self.config.model {
+                PredictionModel::Linear => h.velocity().map(|v| (v, PhysicalPosition::default())),
+                PredictionModel::Quadratic => h.velocity_and_acceleration(),
+            })
+        else {
+            return Vec::new();
+        };
+        let PredictionConfig {
+            look_ahead,
+            interval,
+            ..
+        } = self.config;
+        if look_ahead == 0 {
+            return Vec::new();
+        }
+        let interval = if interval == 0 { look_ahead } else { interval };
+        (1..=look_ahead.div_ceil(interval))
+            .map(|k| {
+                let dt = k.saturating_mul(interval).min(look_ahead);
+                let secs = dt as f64 * 1e-9;
+                PointerState {
+                    time: current.time.saturating_add(dt),
+                    position: PhysicalPosition {
+                        x: current.position.x
+                            + velocity.x * secs
+                            + 0.5 * acceleration.x * secs * secs,
+                        y: current.position.y
+                            + velocity.y * secs
+                            + 0.5 * acceleration.y * secs * secs,
+                    },
+                    ..current.clone()
+                }
+            })
+            .collect()
+    }
+
+    fn record(&mut self, pointer_id: Option<PointerId>, state: &PointerState) {
+        let index = match self.histories.iter().position(|(id, _)| *id == pointer_id) {
+            Some(index) => index,
+            None => {
+                let history = MotionHistory::default()
+                    .with_horizon(self.config.horizon)
+                    .with_max_gap(self.config.max_gap)
+                    .with_max_samples(MAX_SAMPLES);
+                self.histories.push((pointer_id, history));
+                self.histories.len() - 1
+            }
+        };
+        self.histories[index]
+            .1
+            .add_sample(state.time, state.position);
+    }
+
+    fn remove(&mut self, pointer_id: Option<PointerId>) {
+        self.histories.retain(|(id, _)| *id != pointer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointer::{PointerButton, PointerButtonEvent, PointerInfo};
+    use alloc::vec;
+
+    const MS: u64 = 1_000_000;
+
+    fn info(id: u64, pointer_type: PointerType) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type,
+        }
+    }
+
+    fn state(time: u64, x: f64, y: f64) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y },
+            pressure: 0.5,
+            ..Default::default()
+        }
+    }
+
+    fn down(id: u64, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(id, PointerType::Touch),
+            state: state(time, x, y),
+        })
+    }
+
+    fn move_to(id: u64, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer: info(id, PointerType::Touch),
+            current: state(time, x, y),
+            coalesced: vec![],
+            predicted: vec![],
+        })
+    }
+
+    fn predicted(e: PointerEvent) -> Vec<PointerState> {
+        match e {
+            PointerEvent::Move(update) => update.predicted,
+            other => panic!("expected a move, got {other:?}"),
+        }
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "expected {a} ~= {b}");
+    }
+
+    #[test]
+    fn linear_extrapolation() {
+        let mut p = MotionPredictor::default();
+        p.attach_predictions(down(1, 0, 0.0, 0.0));
+        let mut last = vec![];
+        for i in 1..=4 {
+            last = predicted(p.attach_predictions(move_to(
+                1,
+                i * 8 * MS,
+                i as f64 * 8.0,
+                i as f64 * -4.0,
+            )));
+        }
+        // 1 px/ms right and 0.5 px/ms up, predicted at 8ms and 16ms.
+        assert_eq!(last.len(), 2);
+        assert_eq!(last[0].time, 40 * MS);
+        assert_eq!(last[1].time, 48 * MS);
+        assert_approx(last[0].position.x, 40.0);
+        assert_approx(last[0].position.y, -20.0);
+        assert_approx(last[1].position.x, 48.0);
+        assert_approx(last[1].position.y, -24.0);
+        // Other fields come from the current state.
+        assert_eq!(last[1].pressure, 0.5);
+    }
+
+    #[test]
+    fn quadratic_extrapolation() {
+        let config = PredictionConfig::default()
+            .with_look_ahead(10 * MS)
+            .with_interval(0)
+            .with_model(PredictionModel::Quadratic);
+        let mut p = MotionPredictor::new(config);
+        let mut last = vec![];
+        // x = t², in milliseconds.
+        for i in 0..=5_u64 {
+            let t = (i * 4) as f64;
+            last = predicted(p.attach_predictions(move_to(1, i * 4 * MS, t * t, 0.0)));
+        }
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].time, 30 * MS);
+        assert_approx(last[0].position.x, 900.0);
+        assert_approx(last[0].position.y, 0.0);
+    }
+
+    #[test]
+    fn uses_coalesced_and_keeps_platform_predictions() {
+        let mut p = MotionPredictor::new(PredictionConfig::default().with_interval(16 * MS));
+        // A single move with coalesced states is enough to predict.
+        let e = p.attach_predictions(PointerEvent::Move(PointerUpdate {
+            pointer: info(1, PointerType::Pen),
+            current: state(16 * MS, 32.0, 0.0),
+            coalesced: vec![state(0, 0.0, 0.0), state(8 * MS, 16.0, 0.0)],
+            predicted: vec![],
+        }));
+        let last = predicted(e);
+        assert_eq!(last.len(), 1);
+        assert_approx(last[0].position.x, 64.0);
+
+        let platform = vec![state(20 * MS, 1.0, 1.0)];
+        let e = p.attach_predictions(PointerEvent::Move(PointerUpdate {
+            pointer: info(1, PointerType::Pen),
+            current: state(18 * MS, 36.0, 0.0),
+            coalesced: vec![],
+            predicted: platform.clone(),
+        }));
+        assert_eq!(predicted(e), platform);
+    }
+
+    #[test]
+    fn history_is_discarded() {
+        let mut p = MotionPredictor::default();
+        p.attach_predictions(down(1, 0, 0.0, 0.0));
+        assert!(!predicted(p.attach_predictions(move_to(1, 8 * MS, 8.0, 0.0))).is_empty());
+
+        // A long pause starts over.
+        assert!(predicted(p.attach_predictions(move_to(1, 100 * MS, 8.0, 0.0))).is_empty());
+
+        // Pointers are tracked separately.
+        assert!(predicted(p.attach_predictions(move_to(2, 104 * MS, 0.0, 0.0))).is_empty());
+        assert!(!predicted(p.attach_predictions(move_to(1, 104 * MS, 12.0, 0.0))).is_empty());
+
+        // Lifting a touch forgets it.
+        p.attach_predictions(PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(1, PointerType::Touch),
+            state: state(108 * MS, 16.0, 0.0),
+        }));
+        assert!(predicted(p.attach_predictions(move_to(1, 112 * MS, 20.0, 0.0))).is_empty());
+
+        p.attach_predictions(PointerEvent::Cancel(info(1, PointerType::Touch)));
+        assert!(predicted(p.attach_predictions(move_to(1, 116 * MS, 24.0, 0.0))).is_empty());
+    }
+}
@@ -13,6 +13,7 @@ use dpi::PhysicalPosition;
 /// For scroll deltas generated by scrollbars or other elements, `PageDelta`
 /// may be used (for example, when clicking in the well of the scrollbar).
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScrollDelta {
     /// Page delta.
     ///
@@ -22,7 +23,13 @@ pub enum ScrollDelta {
     /// Line delta.
     LineDelta(f32, f32),
     /// Pixel delta.
-    PixelDelta(PhysicalPosition<f64>),
+    PixelDelta(
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_dpi::PhysicalPositionDef")
+        )]
+        PhysicalPosition<f64>,
+    ),
 }
 
 impl ScrollDelta {
@@ -102,6 +109,47 @@ impl ScrollDelta {
     }
 }
 
+/// Where a scroll event falls within a scroll gesture.
+///
+/// Precise devices like touchpads report when the fingers start and stop
+/// scrolling, and some platforms continue with synthetic momentum events.
+/// Notched wheels have no gestures, so each of their events is
+/// [`ScrollPhase::Changed`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollPhase {
+    /// The first event of a scroll gesture.
+    Started,
+    /// A scroll within a gesture, or a scroll that is not part of a gesture.
+    #[default]
+    Changed,
+    /// The last event of a scroll gesture, such as fingers lifting from a touchpad.
+    ///
+    /// This may carry a delta, and may be followed by momentum.
+    Ended,
+    /// The scroll gesture was cancelled, for example by the system, rather than
+    /// ending normally.
+    ///
+    /// Momentum should not follow, and any scrolling past the bounds should
+    /// spring back.
+    Cancelled,
+    /// Scrolling synthesized by the platform after a gesture ended.
+    Momentum,
+}
+
+/// The kind of device that produced a scroll event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollSource {
+    /// The device is not known.
+    #[default]
+    Unknown,
+    /// A mouse wheel, scrolling in discrete steps.
+    Wheel,
+    /// A touchpad or other precise device, scrolling continuously.
+    Touchpad,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
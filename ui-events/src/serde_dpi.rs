@@ -0,0 +1,55 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Serialization of the [`dpi`] types in events.
+//!
+//! The `serde` feature of `dpi` needs `std`, so these mirror its derived
+//! implementations, keeping the same format, for use with `#[serde(with)]`.
+
+use dpi::{PhysicalPosition, PhysicalSize};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PhysicalPosition<f64>", rename = "PhysicalPosition")]
+pub(crate) struct PhysicalPositionDef {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PhysicalSize<f64>", rename = "PhysicalSize")]
+pub(crate) struct PhysicalSizeDef {
+    width: f64,
+    height: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScrollDelta;
+    use crate::pointer::PointerState;
+
+    #[test]
+    fn same_format_as_dpi() {
+        let delta = ScrollDelta::PixelDelta(PhysicalPosition::new(1.5, -2.0));
+        let json = serde_json::to_string(&delta).unwrap();
+        assert_eq!(json, r#"{"PixelDelta":{"x":1.5,"y":-2.0}}"#);
+        assert_eq!(serde_json::from_str::<ScrollDelta>(&json).unwrap(), delta);
+
+        let state = PointerState {
+            position: PhysicalPosition::new(3.0, 4.0),
+            contact_geometry: PhysicalSize::new(5.0, 6.0),
+            ..Default::default()
+        };
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["position"], serde_json::json!({ "x": 3.0, "y": 4.0 }));
+        assert_eq!(
+            value["contact_geometry"],
+            serde_json::json!({ "width": 5.0, "height": 6.0 })
+        );
+        assert_eq!(
+            serde_json::from_value::<PointerState>(value).unwrap(),
+            state
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Text input and composition
+//!
+//! Many scripts cannot be typed with one key press per character. Instead an
+//! input method editor (IME) lets the user compose text over several key presses,
+//! showing the text being composed, known as the preedit, in place until it is
+//! committed. This module contains types describing that process, modeled after
+//! the W3C [`CompositionEvent`]. Edits requested by the text input system, such as
+//! inserting text or deleting a word, are described by [`TextInputEvent`].
+//!
+//! A typical composition, such as typing `啊` with a pinyin IME, looks like:
+//!
+//! 1. [`ImeEvent::Enabled`] when a text input gains focus.
+//! 2. [`ImeEvent::Preedit`] each time the composed text or its cursor changes.
+//! 3. [`ImeEvent::Preedit`] with empty text, then [`ImeEvent::Commit`] with the final text.
+//! 4. [`ImeEvent::Disabled`] when the text input loses focus.
+//!
+//! Backends that cannot tell when an IME is enabled, such as `ui-events-web`,
+//! report every composition as going through all four steps.
+//!
+//! While a composition is in progress, key events that the IME consumes carry
+//! [`KeyboardEvent::is_composing`] set, and should not be treated as text input or shortcuts.
+//!
+//! ## Example:
+//!
+//! ```
+//! use ui_events::text_input::ImeEvent;
+//!
+//! fn handle(text: &mut String, preedit: &mut String, e: ImeEvent) {
+//!     match e {
+//!         ImeEvent::Preedit { text, .. } => *preedit = text,
+//!         ImeEvent::Commit(committed) => text.push_str(&committed),
+//!         ImeEvent::Disabled => preedit.clear(),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+//! [`CompositionEvent`]: https://w3c.github.io/uievents/#events-compositionevents
+//! [`KeyboardEvent::is_composing`]: crate::keyboard::KeyboardEvent::is_composing
+
+extern crate alloc;
+use alloc::string::String;
+use core::ops::Range;
+
+/// An input method editor (IME) event.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImeEvent {
+    /// The IME was enabled, so composition events may follow.
+    ///
+    /// The DOM has no equivalent, so `ui-events-web` reports `compositionstart` as this.
+    Enabled,
+    /// The text being composed changed.
+    ///
+    /// This corresponds to `compositionupdate` in the DOM.
+    /// Empty `text` ends the composition, either because it was cancelled or
+    /// because a [`ImeEvent::Commit`] follows.
+    Preedit {
+        /// The text being composed, to be shown in place of the text input's selection.
+        text: String,
+        /// The cursor or selection within `text`, as a byte range.
+        ///
+        /// `None` means the cursor should be hidden.
+        cursor: Option<Range<usize>>,
+    },
+    /// Text should be inserted at the cursor, ending any composition.
+    ///
+    /// This corresponds to `compositionend` in the DOM.
+    Commit(String),
+    /// The IME was disabled, so any text being composed should be cleared.
+    ///
+    /// The DOM has no equivalent, so `ui-events-web` reports this after each `compositionend`.
+    Disabled,
+}
+
+impl ImeEvent {
+    /// Return `true` if a composition is in progress after this event.
+    ///
+    /// Use this to keep [`KeyboardEvent::is_composing`] up to date.
+    ///
+    /// [`KeyboardEvent::is_composing`]: crate::keyboard::KeyboardEvent::is_composing
+    pub fn is_composing(&self) -> bool {
+        matches!(self, Self::Preedit { text, .. } if !text.is_empty())
+    }
+}
+
+/// The kind of edit a [`TextInputEvent`] describes.
+///
+/// These are the `inputType` values from W3C [Input Events]. Values that are not
+/// listed here, such as the rich text formatting commands, are kept in [`InputType::Other`].
+///
+/// [Input Events]: https://w3c.github.io/input-events/#interface-InputEvent-Attributes
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum InputType {
+    /// Insert typed plain text.
+    InsertText,
+    /// Replace existing text, for example from a spell checker suggestion.
+    InsertReplacementText,
+    /// Insert a line break.
+    InsertLineBreak,
+    /// Insert a paragraph break.
+    InsertParagraph,
+    /// Insert a numbered list.
+    InsertOrderedList,
+    /// Insert a bulleted list.
+    InsertUnorderedList,
+    /// Insert a horizontal rule.
+    InsertHorizontalRule,
+    /// Replace the selection with content from the kill buffer.
+    InsertFromYank,
+    /// Insert content by drag and drop.
+    InsertFromDrop,
+    /// Paste content from the clipboard.
+    InsertFromPaste,
+    /// Paste content from the clipboard as a quotation.
+    InsertFromPasteAsQuotation,
+    /// Transpose the last two characters typed.
+    InsertTranspose,
+    /// Replace the current composition text.
+    InsertCompositionText,
+    /// Insert a link.
+    InsertLink,
+    /// Delete a word before the cursor.
+    DeleteWordBackward,
+    /// Delete a word after the cursor.
+    DeleteWordForward,
+    /// Delete from the cursor to the start of the visual line.
+    DeleteSoftLineBackward,
+    /// Delete from the cursor to the end of the visual line.
+    DeleteSoftLineForward,
+    /// Delete the visual line containing the cursor.
+    DeleteEntireSoftLine,
+    /// Delete from the cursor to the start of the paragraph.
+    DeleteHardLineBackward,
+    /// Delete from the cursor to the end of the paragraph.
+    DeleteHardLineForward,
+    /// Remove content by dragging it elsewhere.
+    DeleteByDrag,
+    /// Cut content to the clipboard.
+    DeleteByCut,
+    /// Delete the selection without specifying a direction.
+    DeleteContent,
+    /// Delete the content before the cursor, as with Backspace.
+    DeleteContentBackward,
+    /// Delete the content after the cursor, as with Delete.
+    DeleteContentForward,
+    /// Undo the last edit.
+    HistoryUndo,
+    /// Redo the last undone edit.
+    HistoryRedo,
+    /// Any other `inputType`, by name.
+    Other(String),
+}
+
+impl InputType {
+    /// Look up an `inputType` name, such as `"insertText"`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "insertText" => Self::InsertText,
+            "insertReplacementText" => Self::InsertReplacementText,
+            "insertLineBreak" => Self::InsertLineBreak,
+            "insertParagraph" => Self::InsertParagraph,
+            "insertOrderedList" => Self::InsertOrderedList,
+            "insertUnorderedList" => Self::InsertUnorderedList,
+            "insertHorizontalRule" => Self::InsertHorizontalRule,
+            "insertFromYank" => Self::InsertFromYank,
+            "insertFromDrop" => Self::InsertFromDrop,
+            "insertFromPaste" => Self::InsertFromPaste,
+            "insertFromPasteAsQuotation" => Self::InsertFromPasteAsQuotation,
+            "insertTranspose" => Self::InsertTranspose,
+            "insertCompositionText" => Self::InsertCompositionText,
+            "insertLink" => Self::InsertLink,
+            "deleteWordBackward" => Self::DeleteWordBackward,
+            "deleteWordForward" => Self::DeleteWordForward,
+            "deleteSoftLineBackward" => Self::DeleteSoftLineBackward,
+            "deleteSoftLineForward" => Self::DeleteSoftLineForward,
+            "deleteEntireSoftLine" => Self::DeleteEntireSoftLine,
+            "deleteHardLineBackward" => Self::DeleteHardLineBackward,
+            "deleteHardLineForward" => Self::DeleteHardLineForward,
+            "deleteByDrag" => Self::DeleteByDrag,
+            "deleteByCut" => Self::DeleteByCut,
+            "deleteContent" => Self::DeleteContent,
+            "deleteContentBackward" => Self::DeleteContentBackward,
+            "deleteContentForward" => Self::DeleteContentForward,
+            "historyUndo" => Self::HistoryUndo,
+            "historyRedo" => Self::HistoryRedo,
+            other => Self::Other(other.into()),
+        }
+    }
+
+    /// The `inputType` name, such as `"insertText"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InsertText => "insertText",
+            Self::InsertReplacementText => "insertReplacementText",
+            Self::InsertLineBreak => "insertLineBreak",
+            Self::InsertParagraph => "insertParagraph",
+            Self::InsertOrderedList => "insertOrderedList",
+            Self::InsertUnorderedList => "insertUnorderedList",
+            Self::InsertHorizontalRule => "insertHorizontalRule",
+            Self::InsertFromYank => "insertFromYank",
+            Self::InsertFromDrop => "insertFromDrop",
+            Self::InsertFromPaste => "insertFromPaste",
+            Self::InsertFromPasteAsQuotation => "insertFromPasteAsQuotation",
+            Self::InsertTranspose => "insertTranspose",
+            Self::InsertCompositionText => "insertCompositionText",
+            Self::InsertLink => "insertLink",
+            Self::DeleteWordBackward => "deleteWordBackward",
+            Self::DeleteWordForward => "deleteWordForward",
+            Self::DeleteSoftLineBackward => "deleteSoftLineBackward",
+            Self::DeleteSoftLineForward => "deleteSoftLineForward",
+            Self::DeleteEntireSoftLine => "deleteEntireSoftLine",
+            Self::DeleteHardLineBackward => "deleteHardLineBackward",
+            Self::DeleteHardLineForward => "deleteHardLineForward",
+            Self::DeleteByDrag => "deleteByDrag",
+            Self::DeleteByCut => "deleteByCut",
+            Self::DeleteContent => "deleteContent",
+            Self::DeleteContentBackward => "deleteContentBackward",
+            Self::DeleteContentForward => "deleteContentForward",
+            Self::HistoryUndo => "historyUndo",
+            Self::HistoryRedo => "historyRedo",
+            Self::Other(name) => name,
+        }
+    }
+}
+
+/// An edit requested by the platform's text input system.
+///
+/// This is modeled after the W3C [`InputEvent`], and lets text fields that do their
+/// own editing, such as those drawn on a canvas, handle typing, deletion, paste, and
+/// composition without interpreting raw key events.
+///
+/// [`InputEvent`]: https://w3c.github.io/uievents/#events-inputevents
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextInputEvent {
+    /// The kind of edit.
+    pub input_type: InputType,
+    /// The text to insert, if the edit inserts plain text.
+    pub data: Option<String>,
+    /// Whether the edit is part of a composition.
+    pub is_composing: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_type_names_round_trip() {
+        for name in [
+            "insertText",
+            "deleteWordBackward",
+            "historyRedo",
+            "formatBold",
+        ] {
+            assert_eq!(InputType::from_name(name).as_str(), name);
+        }
+        assert_eq!(InputType::from_name("insertText"), InputType::InsertText);
+        assert_eq!(
+            InputType::from_name("formatBold"),
+            InputType::Other("formatBold".into())
+        );
+    }
+}
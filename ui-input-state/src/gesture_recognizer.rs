@@ -0,0 +1,867 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Recognizing high-level gestures from pointer events.
+//!
+//! [`GestureRecognizer`] consumes raw [`PointerEvent`]s and produces
+//! [`Gesture`]s: taps (with a multi-tap count), long presses, drags, flings,
+//! and two-finger pinch and rotate synthesized from touch contacts.
+//!
+//! Recognition is driven entirely by [`PointerState::time`], so it is
+//! deterministic and can be tested with synthetic timestamps. Since a long
+//! press can complete without any new event arriving, call
+//! [`GestureRecognizer::poll`] with the current time once per frame.
+//!
+//! Distances in [`GestureConfig`] are in logical pixels, and are converted
+//! using each state's `scale_factor`. Times are in nanoseconds.
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::{Gesture, GestureRecognizer};
+//! use ui_events::pointer::PointerEvent;
+//!
+//! let mut recognizer = GestureRecognizer::default();
+//! # let (events, now): (Vec<PointerEvent>, u64) = unimplemented!();
+//! for e in &events {
+//!     for g in recognizer.process_pointer_event(e) {
+//!         match g {
+//!             Gesture::Tap { count: 2, .. } => { /* Double tap */ }
+//!             Gesture::Pinch { delta, .. } => { let _ = delta; }
+//!             _ => {}
+//!         }
+//!     }
+//! }
+//! for g in recognizer.poll(now) {
+//!     if let Gesture::LongPress { position, .. } = g {
+//!         let _ = position;
+//!     }
+//! }
+//! ```
+extern crate alloc;
+use alloc::vec::Vec;
+
+use ui_events::pointer::{
+    PointerButton, PointerButtonEvent, PointerEvent, PointerInfo, PointerState, PointerType,
+    PointerUpdate,
+};
+
+use dpi::PhysicalPosition;
+
+use crate::math::{atan2, hypot};
+use crate::{VelocityTracker, VelocityTrackerConfig};
+
+/// Configuration for a [`GestureRecognizer`].
+///
+/// Distances are in logical pixels, times are in nanoseconds, and
+/// velocities are in logical pixels per second.
+#[derive(Clone, Copy, Debug)]
+pub struct GestureConfig {
+    /// How far a touch or pen contact may move before it becomes a drag.
+    pub touch_slop: f64,
+    /// How far a mouse may move with a button held before it becomes a drag.
+    pub mouse_slop: f64,
+    /// How long a contact must be held still to become a long press.
+    pub long_press_timeout: u64,
+    /// Maximum time between one tap ending and the next starting, for them
+    /// to count as a multi-tap.
+    pub multi_tap_timeout: u64,
+    /// Maximum distance between consecutive taps of a multi-tap.
+    pub multi_tap_slop: f64,
+    /// Minimum release speed for a drag to end in a [`Gesture::Fling`].
+    pub min_fling_velocity: f64,
+    /// How far back in time samples are considered when estimating
+    /// the release velocity of a drag, with a [`VelocityTracker`].
+    pub velocity_horizon: u64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            touch_slop: 8.0,
+            mouse_slop: 3.0,
+            long_press_timeout: 500_000_000,
+            multi_tap_timeout: 300_000_000,
+            multi_tap_slop: 32.0,
+            min_fling_velocity: 50.0,
+            velocity_horizon: 100_000_000,
+        }
+    }
+}
+
+impl GestureConfig {
+    /// Set the touch and pen slop (builder style).
+    pub fn with_touch_slop(mut self, slop: f64) -> Self {
+        self.touch_slop = slop;
+        self
+    }
+
+    /// Set the mouse slop (builder style).
+    pub fn with_mouse_slop(mut self, slop: f64) -> Self {
+        self.mouse_slop = slop;
+        self
+    }
+
+    /// Set the long press timeout (builder style).
+    pub fn with_long_press_timeout(mut self, timeout: u64) -> Self {
+        self.long_press_timeout = timeout;
+        self
+    }
+
+    /// Set the multi-tap timeout (builder style).
+    pub fn with_multi_tap_timeout(mut self, timeout: u64) -> Self {
+        self.multi_tap_timeout = timeout;
+        self
+    }
+
+    /// Set the multi-tap slop (builder style).
+    pub fn with_multi_tap_slop(mut self, slop: f64) -> Self {
+        self.multi_tap_slop = slop;
+        self
+    }
+
+    /// Set the minimum fling velocity (builder style).
+    pub fn with_min_fling_velocity(mut self, velocity: f64) -> Self {
+        self.min_fling_velocity = velocity;
+        self
+    }
+
+    /// Set the velocity estimation horizon (builder style).
+    pub fn with_velocity_horizon(mut self, horizon: u64) -> Self {
+        self.velocity_horizon = horizon;
+        self
+    }
+
+    fn slop(&self, pointer_type: PointerType) -> f64 {
+        match pointer_type {
+            PointerType::Touch | PointerType::Pen => self.touch_slop,
+            _ => self.mouse_slop,
+        }
+    }
+}
+
+/// A recognized gesture.
+///
+/// Positions are in physical pixels, and velocities in physical pixels per second.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gesture {
+    /// A press and release without significant motion.
+    Tap {
+        /// The pointer that tapped.
+        pointer: PointerInfo,
+        /// The button that was pressed, if any.
+        button: Option<PointerButton>,
+        /// Where the tap was released.
+        position: PhysicalPosition<f64>,
+        /// Number of taps in quick succession, starting at 1.
+        count: u8,
+    },
+    /// A contact held still for [`GestureConfig::long_press_timeout`].
+    ///
+    /// A long press is not followed by a [`Gesture::Tap`] when released.
+    LongPress {
+        /// The pointer that is pressed.
+        pointer: PointerInfo,
+        /// The button that is pressed, if any.
+        button: Option<PointerButton>,
+        /// Where the pointer is pressed.
+        position: PhysicalPosition<f64>,
+    },
+    /// A pressed pointer moved beyond the slop and began dragging.
+    DragStart {
+        /// The dragging pointer.
+        pointer: PointerInfo,
+        /// The button that is pressed, if any.
+        button: Option<PointerButton>,
+        /// Where the pointer was pressed.
+        start: PhysicalPosition<f64>,
+        /// Where the pointer is now.
+        position: PhysicalPosition<f64>,
+    },
+    /// A dragging pointer moved.
+    DragUpdate {
+        /// The dragging pointer.
+        pointer: PointerInfo,
+        /// Where the pointer is now.
+        position: PhysicalPosition<f64>,
+        /// Motion since the last drag gesture.
+        delta: PhysicalPosition<f64>,
+    },
+    /// A dragging pointer was released.
+    DragEnd {
+        /// The dragging pointer.
+        pointer: PointerInfo,
+        /// Where the pointer was released.
+        position: PhysicalPosition<f64>,
+        /// Estimated velocity at release.
+        velocity: PhysicalPosition<f64>,
+    },
+    /// A drag ended without a release, because the pointer was cancelled
+    /// or a second finger turned it into a pinch.
+    ///
+    /// You should try to undo the effect of the drag when you receive this.
+    DragCancel {
+        /// The dragging pointer.
+        pointer: PointerInfo,
+    },
+    /// A drag was released faster than [`GestureConfig::min_fling_velocity`].
+    ///
+    /// This follows the corresponding [`Gesture::DragEnd`].
+    Fling {
+        /// The pointer that was flung.
+        pointer: PointerInfo,
+        /// Estimated velocity at release.
+        velocity: PhysicalPosition<f64>,
+    },
+    /// Two touch contacts moved closer together or further apart.
+    Pinch {
+        /// Midpoint between the contacts.
+        center: PhysicalPosition<f64>,
+        /// Scale delta, with the same meaning as [`PointerGesture::Pinch`].
+        ///
+        /// [`PointerGesture::Pinch`]: ui_events::pointer::PointerGesture::Pinch
+        delta: f32,
+    },
+    /// Two touch contacts rotated around each other.
+    Rotate {
+        /// Midpoint between the contacts.
+        center: PhysicalPosition<f64>,
+        /// Clockwise rotation delta in radians, with the same meaning as
+        /// [`PointerGesture::Rotate`].
+        ///
+        /// [`PointerGesture::Rotate`]: ui_events::pointer::PointerGesture::Rotate
+        delta: f32,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct Contact {
+    /// Identifying information about the pointer.
+    pointer: PointerInfo,
+    /// The button that started the contact.
+    button: Option<PointerButton>,
+    /// Time of the `Down`.
+    down_time: u64,
+    /// Position of the `Down`.
+    down_position: PhysicalPosition<f64>,
+    /// Position of the last drag gesture, or of the `Down`.
+    last_position: PhysicalPosition<f64>,
+    /// Scale factor of the last state.
+    scale_factor: f64,
+    /// Recent positions, for velocity estimation.
+    tracker: VelocityTracker,
+    /// Moved beyond the slop.
+    dragging: bool,
+    /// A long press was recognized.
+    long_pressed: bool,
+    /// Part of a multi-touch gesture, so no single-pointer gestures apply.
+    multi: bool,
+}
+
+/// The last recognized tap, for multi-tap counting.
+#[derive(Clone, Copy, Debug)]
+struct LastTap {
+    pointer_type: PointerType,
+    button: Option<PointerButton>,
+    time: u64,
+    position: PhysicalPosition<f64>,
+    count: u8,
+}
+
+/// Recognizes [`Gesture`]s from a stream of [`PointerEvent`]s.
+#[derive(Clone, Debug, Default)]
+pub struct GestureRecognizer {
+    /// Configuration.
+    config: GestureConfig,
+    /// Pressed pointers.
+    contacts: Vec<Contact>,
+    /// The last tap.
+    last_tap: Option<LastTap>,
+    /// Distance and angle between the first two touch contacts.
+    span: Option<(f64, f64)>,
+}
+
+fn distance(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> f64 {
+    hypot(a.x - b.x, a.y - b.y)
+}
+
+fn midpoint(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> PhysicalPosition<f64> {
+    PhysicalPosition {
+        x: (a.x + b.x) * 0.5,
+        y: (a.y + b.y) * 0.5,
+    }
+}
+
+/// Wrap a difference of two angles from `atan2` into `(-π, π]`.
+fn wrap_angle(a: f64) -> f64 {
+    use core::f64::consts::{PI, TAU};
+    if a > PI {
+        a - TAU
+    } else if a <= -PI {
+        a + TAU
+    } else {
+        a
+    }
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the given configuration.
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// The configuration.
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Return `true` if any pointer is pressed.
+    pub fn is_active(&self) -> bool {
+        !self.contacts.is_empty()
+    }
+
+    /// Recognize gestures that complete with the passage of time.
+    ///
+    /// `time` is in the same time base as [`PointerState::time`].
+    /// Call this once per frame so that long presses are recognized
+    /// while the pointer is held still.
+    pub fn poll(&mut self, time: u64) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        for c in &mut self.contacts {
+            if !c.dragging
+                && !c.long_pressed
+                && !c.multi
+                && time.saturating_sub(c.down_time) >= self.config.long_press_timeout
+            {
+                c.long_pressed = true;
+                out.push(Gesture::LongPress {
+                    pointer: c.pointer,
+                    button: c.button,
+                    position: c.last_position,
+                });
+            }
+        }
+        out
+    }
+
+    /// Update the recognizer with an event, and return any gestures it completes.
+    ///
+    /// Events without a [`PointerId`] are ignored.
+    ///
+    /// [`PointerId`]: ui_events::pointer::PointerId
+    pub fn process_pointer_event(&mut self, event: &PointerEvent) -> Vec<Gesture> {
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button,
+                pointer,
+                state,
+            }) => self.down(*pointer, *button, state),
+            PointerEvent::Move(PointerUpdate {
+                pointer,
+                current,
+                coalesced,
+                ..
+            }) => self.motion(pointer, coalesced, current),
+            PointerEvent::Up(PointerButtonEvent { pointer, state, .. }) => self.up(pointer, state),
+            PointerEvent::Cancel(pointer) => self.cancel(pointer),
+            _ => Vec::new(),
+        }
+    }
+
+    fn index(&self, pointer: &PointerInfo) -> Option<usize> {
+        let id = pointer.pointer_id?;
+        self.contacts
+            .iter()
+            .position(|c| c.pointer.pointer_id == Some(id))
+    }
+
+    fn touches(&self) -> impl Iterator<Item = &Contact> {
+        self.contacts
+            .iter()
+            .filter(|c| c.pointer.pointer_type == PointerType::Touch)
+    }
+
+    /// Distance and angle between the first two touch contacts.
+    fn measure_span(&self) -> Option<(f64, f64, PhysicalPosition<f64>)> {
+        let mut touches = self.touches();
+        let a = touches.next()?.last_position;
+        let b = touches.next()?.last_position;
+        Some((distance(a, b), atan2(b.y - a.y, b.x - a.x), midpoint(a, b)))
+    }
+
+    fn down(
+        &mut self,
+        pointer: PointerInfo,
+        button: Option<PointerButton>,
+        state: &PointerState,
+    ) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        if pointer.pointer_id.is_none() {
+            return out;
+        }
+        if self.index(&pointer).is_some() {
+            // Another button on an already pressed pointer, such as a
+            // mouse chord, does not start a new gesture.
+            return out;
+        }
+        self.contacts.push(Contact {
+            pointer,
+            button,
+            down_time: state.time,
+            down_position: state.position,
+            last_position: state.position,
+            scale_factor: state.scale_factor,
+            tracker: {
+                let mut tracker = VelocityTracker::new(
+                    VelocityTrackerConfig::default().with_horizon(self.config.velocity_horizon),
+                );
+                tracker.add_sample(state.time, state.position);
+                tracker
+            },
+            dragging: false,
+            long_pressed: false,
+            multi: false,
+        });
+
+        if pointer.pointer_type == PointerType::Touch && self.touches().count() >= 2 {
+            for c in &mut self.contacts {
+                if c.pointer.pointer_type != PointerType::Touch {
+                    continue;
+                }
+                if c.dragging {
+                    out.push(Gesture::DragCancel { pointer: c.pointer });
+                    c.dragging = false;
+                }
+                c.multi = true;
+            }
+            self.span = self.measure_span().map(|(d, a, _)| (d, a));
+        }
+        out
+    }
+
+    fn motion(
+        &mut self,
+        pointer: &PointerInfo,
+        coalesced: &[PointerState],
+        current: &PointerState,
+    ) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        let Some(idx) = self.index(pointer) else {
+            return out;
+        };
+        let c = &mut self.contacts[idx];
+        for s in coalesced.iter().chain(core::iter::once(current)) {
+            c.tracker.add_sample(s.time, s.position);
+        }
+        c.scale_factor = current.scale_factor;
+
+        if c.multi {
+            c.last_position = current.position;
+            if let (Some((d0, a0)), Some((d1, a1, center))) = (self.span, self.measure_span()) {
+                #[expect(
+                    clippy::cast_possible_truncation,
+                    reason = "Gesture deltas are stored as f32, like PointerGesture."
+                )]
+                {
+                    if d0 > 0.0 && d1 != d0 {
+                        out.push(Gesture::Pinch {
+                            center,
+                            delta: (d1 / d0 - 1.0) as f32,
+                        });
+                    }
+                    let rotation = wrap_angle(a1 - a0);
+                    if rotation != 0.0 {
+                        out.push(Gesture::Rotate {
+                            center,
+                            delta: rotation as f32,
+                        });
+                    }
+                }
+                self.span = Some((d1, a1));
+            }
+            return out;
+        }
+
+        if !c.dragging {
+            let slop = self.config.slop(c.pointer.pointer_type) * c.scale_factor;
+            if c.long_pressed || distance(c.down_position, current.position) <= slop {
+                return out;
+            }
+            c.dragging = true;
+            out.push(Gesture::DragStart {
+                pointer: c.pointer,
+                button: c.button,
+                start: c.down_position,
+                position: current.position,
+            });
+        } else {
+            out.push(Gesture::DragUpdate {
+                pointer: c.pointer,
+                position: current.position,
+                delta: PhysicalPosition {
+                    x: current.position.x - c.last_position.x,
+                    y: current.position.y - c.last_position.y,
+                },
+            });
+        }
+        c.last_position = current.position;
+        out
+    }
+
+    fn up(&mut self, pointer: &PointerInfo, state: &PointerState) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        let Some(idx) = self.index(pointer) else {
+            return out;
+        };
+        if !state.buttons.is_empty() && pointer.pointer_type == PointerType::Mouse {
+            // Other buttons are still held, so the gesture continues.
+            return out;
+        }
+        // Make sure the release position contributes to the velocity.
+        if !self.contacts[idx].multi && self.contacts[idx].dragging {
+            out.extend(self.motion(pointer, &[], state));
+        }
+        let c = self.contacts.remove(idx);
+
+        if c.multi {
+            if self.touches().count() < 2 {
+                self.span = None;
+            } else {
+                self.span = self.measure_span().map(|(d, a, _)| (d, a));
+            }
+        } else if c.dragging {
+            let velocity = c.tracker.estimate().map(|e| e.velocity).unwrap_or_default();
+            out.push(Gesture::DragEnd {
+                pointer: c.pointer,
+                position: state.position,
+                velocity,
+            });
+            let speed = hypot(velocity.x, velocity.y) / c.scale_factor;
+            if speed >= self.config.min_fling_velocity {
+                out.push(Gesture::Fling {
+                    pointer: c.pointer,
+                    velocity,
+                });
+            }
+        } else if !c.long_pressed
+            && state.time.saturating_sub(c.down_time) < self.config.long_press_timeout
+        {
+            let count = match self.last_tap {
+                Some(last)
+                    if last.pointer_type == c.pointer.pointer_type
+                        && last.button == c.button
+                        && c.down_time.saturating_sub(last.time)
+                            <= self.config.multi_tap_timeout
+                        && distance(last.position, c.down_position)
+                            <= self.config.multi_tap_slop * c.scale_factor =>
+                {
+                    last.count.saturating_add(1)
+                }
+                _ => 1,
+            };
+            self.last_tap = Some(LastTap {
+                pointer_type: c.pointer.pointer_type,
+                button: c.button,
+                time: state.time,
+                position: state.position,
+                count,
+            });
+            out.push(Gesture::Tap {
+                pointer: c.pointer,
+                button: c.button,
+                position: state.position,
+                count,
+            });
+        }
+        out
+    }
+
+    fn cancel(&mut self, pointer: &PointerInfo) -> Vec<Gesture> {
+        let mut out = Vec::new();
+        let Some(idx) = self.index(pointer) else {
+            return out;
+        };
+        let c = self.contacts.remove(idx);
+        if c.dragging {
+            out.push(Gesture::DragCancel { pointer: c.pointer });
+        }
+        if c.multi && self.touches().count() < 2 {
+            self.span = None;
+        }
+        if self
+            .last_tap
+            .is_some_and(|t| t.pointer_type == c.pointer.pointer_type)
+        {
+            self.last_tap = None;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use ui_events::pointer::{PointerButtons, PointerId};
+
+    const MS: u64 = 1_000_000;
+
+    fn info(id: u64, pointer_type: PointerType) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type,
+        }
+    }
+
+    fn state(time: u64, x: f64, y: f64, buttons: PointerButtons) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y },
+            buttons,
+            ..Default::default()
+        }
+    }
+
+    fn down(pointer: PointerInfo, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer,
+            state: state(time, x, y, PointerButton::Primary.into()),
+        })
+    }
+
+    fn up(pointer: PointerInfo, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer,
+            state: state(time, x, y, PointerButtons::default()),
+        })
+    }
+
+    fn move_to(pointer: PointerInfo, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer,
+            current: state(time, x, y, PointerButton::Primary.into()),
+            coalesced: vec![],
+            predicted: vec![],
+        })
+    }
+
+    fn feed(r: &mut GestureRecognizer, events: &[PointerEvent]) -> Vec<Gesture> {
+        events
+            .iter()
+            .flat_map(|e| r.process_pointer_event(e))
+            .collect()
+    }
+
+    #[test]
+    fn single_double_triple_tap() {
+        let mouse = info(1, PointerType::Mouse);
+        let mut r = GestureRecognizer::default();
+        let mut counts = vec![];
+        for i in 0..3 {
+            let t = i * 200 * MS;
+            for g in feed(
+                &mut r,
+                &[
+                    down(mouse, t, 10.0, 10.0),
+                    up(mouse, t + 50 * MS, 11.0, 10.0),
+                ],
+            ) {
+                if let Gesture::Tap { count, .. } = g {
+                    counts.push(count);
+                }
+            }
+        }
+        assert_eq!(counts, [1, 2, 3]);
+
+        // Too late for a fourth.
+        let g = feed(
+            &mut r,
+            &[
+                down(mouse, 2 * 1_000 * MS, 10.0, 10.0),
+                up(mouse, 2_050 * MS, 10.0, 10.0),
+            ],
+        );
+        assert!(matches!(g[..], [Gesture::Tap { count: 1, .. }]));
+    }
+
+    #[test]
+    fn taps_far_apart_do_not_count() {
+        let touch = info(2, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        feed(
+            &mut r,
+            &[down(touch, 0, 0.0, 0.0), up(touch, 10 * MS, 0.0, 0.0)],
+        );
+        let g = feed(
+            &mut r,
+            &[
+                down(touch, 100 * MS, 100.0, 0.0),
+                up(touch, 110 * MS, 100.0, 0.0),
+            ],
+        );
+        assert!(matches!(g[..], [Gesture::Tap { count: 1, .. }]));
+    }
+
+    #[test]
+    fn long_press() {
+        let touch = info(2, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        feed(&mut r, &[down(touch, 0, 5.0, 5.0)]);
+        assert!(r.poll(499 * MS).is_empty());
+        let g = r.poll(500 * MS);
+        assert!(matches!(g[..], [Gesture::LongPress { position, .. }] if position.x == 5.0));
+        // Only recognized once, and no tap follows.
+        assert!(r.poll(600 * MS).is_empty());
+        assert!(feed(&mut r, &[up(touch, 700 * MS, 5.0, 5.0)]).is_empty());
+    }
+
+    #[test]
+    fn drag_and_fling() {
+        let touch = info(2, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        let g = feed(
+            &mut r,
+            &[
+                down(touch, 0, 0.0, 0.0),
+                // Within slop.
+                move_to(touch, 10 * MS, 5.0, 0.0),
+            ],
+        );
+        assert!(g.is_empty());
+
+        let g = feed(&mut r, &[move_to(touch, 20 * MS, 10.0, 0.0)]);
+        assert!(
+            matches!(g[..], [Gesture::DragStart { start, position, .. }] if start.x == 0.0 && position.x == 10.0)
+        );
+
+        let g = feed(&mut r, &[move_to(touch, 30 * MS, 15.0, 0.0)]);
+        assert!(matches!(g[..], [Gesture::DragUpdate { delta, .. }] if delta.x == 5.0));
+
+        // Drags don't become long presses.
+        assert!(r.poll(1_000 * MS).is_empty());
+
+        let g = feed(&mut r, &[up(touch, 40 * MS, 20.0, 0.0)]);
+        let [
+            Gesture::DragUpdate { .. },
+            Gesture::DragEnd { velocity, .. },
+            Gesture::Fling { .. },
+        ] = g[..]
+        else {
+            panic!("expected drag end and fling, got {g:?}");
+        };
+        // A steady 20px in 40ms.
+        assert!((velocity.x - 500.0).abs() < 1e-6, "velocity {velocity:?}");
+    }
+
+    #[test]
+    fn slow_release_does_not_fling() {
+        let mouse = info(1, PointerType::Mouse);
+        let mut r = GestureRecognizer::default();
+        feed(
+            &mut r,
+            &[
+                down(mouse, 0, 0.0, 0.0),
+                move_to(mouse, 100 * MS, 10.0, 0.0),
+                move_to(mouse, 1_000 * MS, 10.0, 0.0),
+            ],
+        );
+        let g = feed(&mut r, &[up(mouse, 1_050 * MS, 10.0, 0.0)]);
+        assert!(
+            !g.iter().any(|g| matches!(g, Gesture::Fling { .. })),
+            "unexpected fling in {g:?}"
+        );
+        assert!(g.iter().any(|g| matches!(g, Gesture::DragEnd { .. })));
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let a = info(2, PointerType::Touch);
+        let b = info(3, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        feed(&mut r, &[down(a, 0, 0.0, 0.0), down(b, 0, 100.0, 0.0)]);
+
+        // Spread to double the distance.
+        let g = feed(&mut r, &[move_to(b, 10 * MS, 200.0, 0.0)]);
+        assert!(
+            matches!(g[..], [Gesture::Pinch { delta, center }] if delta == 1.0 && center.x == 100.0)
+        );
+
+        // Rotate a quarter turn clockwise (Y down).
+        let g = feed(&mut r, &[move_to(b, 20 * MS, 0.0, 200.0)]);
+        let [Gesture::Rotate { delta, .. }] = g[..] else {
+            panic!("expected rotate, got {g:?}");
+        };
+        assert!((delta - core::f32::consts::FRAC_PI_2).abs() < 1e-6);
+
+        // Lifting a finger ends the pinch without a tap.
+        assert!(feed(&mut r, &[up(b, 30 * MS, 0.0, 200.0)]).is_empty());
+        assert!(feed(&mut r, &[move_to(a, 40 * MS, 50.0, 0.0)]).is_empty());
+        assert!(feed(&mut r, &[up(a, 50 * MS, 50.0, 0.0)]).is_empty());
+        assert!(!r.is_active());
+    }
+
+    #[test]
+    fn second_finger_cancels_drag() {
+        let a = info(2, PointerType::Touch);
+        let b = info(3, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        feed(
+            &mut r,
+            &[down(a, 0, 0.0, 0.0), move_to(a, 10 * MS, 50.0, 0.0)],
+        );
+        let g = feed(&mut r, &[down(b, 20 * MS, 100.0, 0.0)]);
+        assert!(matches!(g[..], [Gesture::DragCancel { pointer }] if pointer == a));
+    }
+
+    #[test]
+    fn cancel_during_drag() {
+        let touch = info(2, PointerType::Touch);
+        let mut r = GestureRecognizer::default();
+        feed(
+            &mut r,
+            &[down(touch, 0, 0.0, 0.0), move_to(touch, 10 * MS, 50.0, 0.0)],
+        );
+        let g = feed(&mut r, &[PointerEvent::Cancel(touch)]);
+        assert!(matches!(g[..], [Gesture::DragCancel { .. }]));
+        assert!(!r.is_active());
+    }
+
+    #[test]
+    fn slop_scales_with_config_and_scale_factor() {
+        let touch = info(2, PointerType::Touch);
+        let mut r = GestureRecognizer::new(GestureConfig::default().with_touch_slop(20.0));
+        let g = feed(
+            &mut r,
+            &[down(touch, 0, 0.0, 0.0), move_to(touch, 10 * MS, 15.0, 0.0)],
+        );
+        assert!(g.is_empty());
+
+        let mut r = GestureRecognizer::default();
+        let hidpi = |e: PointerEvent| match e {
+            PointerEvent::Down(mut e) => {
+                e.state.scale_factor = 2.0;
+                PointerEvent::Down(e)
+            }
+            PointerEvent::Move(mut e) => {
+                e.current.scale_factor = 2.0;
+                PointerEvent::Move(e)
+            }
+            e => e,
+        };
+        let g = feed(
+            &mut r,
+            &[
+                hidpi(down(touch, 0, 0.0, 0.0)),
+                hidpi(move_to(touch, 10 * MS, 12.0, 0.0)),
+            ],
+        );
+        // 12 physical pixels is 6 logical pixels, within the 8 pixel slop.
+        assert!(g.is_empty());
+    }
+}
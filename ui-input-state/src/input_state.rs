@@ -3,7 +3,7 @@
 
 //! # Frame-level input state aggregation.
 //!
-//! `InputState` groups together [`PrimaryPointerState`] and [`KeyboardState`]
+//! `InputState` groups together [`PrimaryPointerState`], [`PointerStates`] and [`KeyboardState`]
 //! and provides a single [`clear_frame`](InputState::clear_frame) call to reset
 //! per-frame transitions. Feed events into the contained states as they arrive
 //! from your backend, then query during your UI update pass.
@@ -63,6 +63,7 @@
 //! input.clear_frame();
 //! ```
 use crate::KeyboardState;
+use crate::PointerStates;
 use crate::PrimaryPointerState;
 
 /// A stateful view of the input data for a frame, rather than
@@ -72,6 +73,9 @@ pub struct InputState {
     /// The state of the primary pointer.
     pub primary_pointer: PrimaryPointerState,
 
+    /// The state of all active pointers.
+    pub pointers: PointerStates,
+
     /// The state of the keyboard.
     pub keyboard: KeyboardState,
 }
@@ -80,6 +84,7 @@ impl InputState {
     /// Clear the per-frame state to prepare for a new frame.
     pub fn clear_frame(&mut self) {
         self.primary_pointer.clear_frame();
+        self.pointers.clear_frame();
         self.keyboard.clear_frame();
     }
 }
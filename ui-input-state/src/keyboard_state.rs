@@ -27,6 +27,7 @@
 //! ks.process_keyboard_event(ev);
 //! assert!(ks.key_str_just_pressed("z"));
 //! ```
+use ui_events::keyboard::shortcut::{KeyChord, Shortcut};
 use ui_events::keyboard::{Code, Key, KeyState, KeyboardEvent, Location, Modifiers};
 
 extern crate alloc;
@@ -95,6 +96,24 @@ impl KeyboardState {
         self.just_pressed.iter().any(|KeyInfo(_, _, c)| c == &code)
     }
 
+    /// Return `true` if a key matching `chord` was pressed within the last frame,
+    /// with the chord's modifiers held.
+    ///
+    /// Modifiers are compared against the current [`modifiers`](Self::modifiers).
+    pub fn chord_just_pressed(&self, chord: &KeyChord) -> bool {
+        self.just_pressed
+            .iter()
+            .any(|KeyInfo(k, _, c)| chord.matches_key(k, *c, self.modifiers))
+    }
+
+    /// Return `true` if the single chord `shortcut` was pressed within the last frame.
+    ///
+    /// Shortcuts with more than one chord never match; use a
+    /// [`ShortcutTable`](ui_events::keyboard::shortcut::ShortcutTable) to follow sequences.
+    pub fn shortcut_just_pressed(&self, shortcut: &Shortcut) -> bool {
+        matches!(shortcut.chords(), [chord] if self.chord_just_pressed(chord))
+    }
+
     /// Return `true` if the `key` was released within the last frame with
     /// any [`Location`].
     pub fn key_just_released(&self, key: Key) -> bool {
@@ -230,6 +249,7 @@ impl KeyboardState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ui_events::keyboard::shortcut::ParseOptions;
     use ui_events::keyboard::{Code, NamedKey};
 
     fn make_key_down_event(key: Key) -> KeyboardEvent {
@@ -388,4 +408,24 @@ mod tests {
         assert!(state.code_just_released(Code::KeyA));
         assert!(!state.code_down(Code::KeyA));
     }
+
+    #[test]
+    fn shortcut_just_pressed() {
+        let opts = ParseOptions::default().with_mod(Modifiers::CONTROL);
+        let undo = opts.parse("Mod+Z").unwrap();
+        let mut state = KeyboardState::default();
+
+        let mut event = make_key_down_event(Key::Character("z".into()));
+        state.process_keyboard_event(event.clone());
+        assert!(!state.shortcut_just_pressed(&undo));
+        state.clear_frame();
+
+        event.modifiers = Modifiers::CONTROL;
+        state.process_keyboard_event(event);
+        assert!(state.shortcut_just_pressed(&undo));
+        assert!(state.chord_just_pressed(&undo.chords()[0]));
+        assert!(!state.shortcut_just_pressed(&opts.parse("Mod+Z Mod+Z").unwrap()));
+        state.clear_frame();
+        assert!(!state.shortcut_just_pressed(&undo));
+    }
 }
@@ -0,0 +1,733 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Kinetic (momentum) scrolling.
+//!
+//! [`KineticScroller`] turns [`PointerEvent::Scroll`] events and touch drags
+//! into a stream of [`ScrollDelta::PixelDelta`]s, continuing to scroll with
+//! friction after a fling and bouncing back when scrolled past its bounds.
+//! This gives the same fling behavior on every backend, including those
+//! which only report bare line deltas without momentum.
+//!
+//! Events update the scroller as they arrive, but deltas are only produced
+//! by [`KineticScroller::advance`], which you call once per frame with the
+//! frame time. The simulation runs in fixed steps of one millisecond, so the
+//! output only depends on the events and the frame times, and can be tested
+//! with synthetic timestamps. Keep requesting frames while
+//! [`KineticScroller::is_animating`] returns `true`.
+//!
+//! - A touch drag scrolls directly, and flings when released quickly.
+//! - A stream of scroll events flings when it stops for
+//!   [`KineticScrollConfig::scroll_end_timeout`] after moving quickly, or
+//!   immediately at [`ScrollPhase::Ended`]. Slow or isolated wheel clicks do
+//!   not fling, nor does a gesture ending in [`ScrollPhase::Cancelled`], and
+//!   momentum events from the platform are ignored.
+//! - Any new [`PointerEvent::Down`] or scroll stops a fling.
+//!
+//! Distances in [`KineticScrollConfig`] are in logical pixels, and are
+//! converted using each event's `scale_factor`. Times are in nanoseconds.
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::KineticScroller;
+//! use ui_events::pointer::PointerEvent;
+//! use dpi::PhysicalPosition;
+//!
+//! let mut scroller = KineticScroller::default();
+//! // Content can be scrolled 2000px down, and not horizontally.
+//! scroller.set_bounds(PhysicalPosition::new(0.0, 0.0), PhysicalPosition::new(0.0, 2000.0));
+//! # let (events, frame_time): (Vec<PointerEvent>, u64) = unimplemented!();
+//! for e in &events {
+//!     scroller.process_pointer_event(e);
+//! }
+//! if let Some(delta) = scroller.advance(frame_time) {
+//!     // Scroll your content by `delta`.
+//!     let _ = delta;
+//! }
+//! if scroller.is_animating() {
+//!     // Request another frame.
+//! }
+//! ```
+
+use ui_events::pointer::{
+    PointerButtonEvent, PointerEvent, PointerId, PointerScrollEvent, PointerType, PointerUpdate,
+};
+use ui_events::{ScrollDelta, ScrollPhase};
+
+use dpi::PhysicalPosition;
+
+use crate::{ScrollPolicy, VelocityTracker, VelocityTrackerConfig};
+
+/// Length of one simulation step, in nanoseconds.
+const STEP: u64 = 1_000_000;
+/// Length of one simulation step, in seconds.
+const STEP_SECS: f64 = 1e-3;
+/// Maximum number of simulation steps in one call to [`KineticScroller::advance`].
+const MAX_STEPS: u64 = 1_000;
+
+/// Configuration for a [`KineticScroller`].
+///
+/// Distances are in logical pixels, times are in nanoseconds, and
+/// velocities are in logical pixels per second.
+#[derive(Clone, Copy, Debug)]
+pub struct KineticScrollConfig {
+    /// How line and page scroll deltas are resolved to pixels.
+    pub scroll_policy: ScrollPolicy,
+    /// Rate at which a fling slows down, per second.
+    ///
+    /// The velocity decays by a factor of `e` every `1 / friction` seconds.
+    pub friction: f64,
+    /// Minimum release velocity to start a fling.
+    pub min_fling_velocity: f64,
+    /// Maximum velocity of a fling.
+    pub max_fling_velocity: f64,
+    /// Velocity below which a fling stops.
+    pub stop_velocity: f64,
+    /// How long a stream of scroll events must pause before it is
+    /// considered finished, and may fling.
+    pub scroll_end_timeout: u64,
+    /// Fraction of the input applied while dragging or scrolling past the bounds.
+    pub overscroll_resistance: f64,
+    /// Stiffness of the spring pulling the offset back within the bounds, per second squared.
+    pub overscroll_stiffness: f64,
+    /// Damping of the spring pulling the offset back within the bounds, per second.
+    pub overscroll_damping: f64,
+}
+
+impl Default for KineticScrollConfig {
+    fn default() -> Self {
+        Self {
+            scroll_policy: ScrollPolicy::default(),
+            friction: 2.0,
+            min_fling_velocity: 50.0,
+            max_fling_velocity: 8_000.0,
+            stop_velocity: 10.0,
+            scroll_end_timeout: 50_000_000,
+            overscroll_resistance: 0.5,
+            // Critically damped.
+            overscroll_stiffness: 400.0,
+            overscroll_damping: 40.0,
+        }
+    }
+}
+
+impl KineticScrollConfig {
+    /// Set the scroll policy (builder style).
+    pub fn with_scroll_policy(mut self, policy: ScrollPolicy) -> Self {
+        self.scroll_policy = policy;
+        self
+    }
+
+    /// Set the friction (builder style).
+    pub fn with_friction(mut self, friction: f64) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Set the minimum fling velocity (builder style).
+    pub fn with_min_fling_velocity(mut self, velocity: f64) -> Self {
+        self.min_fling_velocity = velocity;
+        self
+    }
+
+    /// Set the maximum fling velocity (builder style).
+    pub fn with_max_fling_velocity(mut self, velocity: f64) -> Self {
+        self.max_fling_velocity = velocity;
+        self
+    }
+
+    /// Set the scroll end timeout (builder style).
+    pub fn with_scroll_end_timeout(mut self, timeout: u64) -> Self {
+        self.scroll_end_timeout = timeout;
+        self
+    }
+
+    /// Set the overscroll resistance (builder style).
+    pub fn with_overscroll_resistance(mut self, resistance: f64) -> Self {
+        self.overscroll_resistance = resistance;
+        self
+    }
+}
+
+/// What is driving the scroll offset.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Phase {
+    /// Nothing; any overscroll springs back.
+    #[default]
+    Idle,
+    /// A touch drag by the given pointer.
+    Dragging(Option<PointerId>),
+    /// A stream of scroll events, the last at the given time.
+    Scrolling(u64),
+    /// A fling, possibly with overscroll springing back.
+    Momentum,
+}
+
+/// Synthesizes kinetic scrolling from scroll events and touch drags.
+#[derive(Clone, Debug)]
+pub struct KineticScroller {
+    /// Configuration.
+    config: KineticScrollConfig,
+    /// What is driving the scroll offset.
+    phase: Phase,
+    /// Estimates the velocity of the direct input.
+    tracker: VelocityTracker,
+    /// Sum of all direct input, without overscroll resistance, for velocity estimation.
+    input: PhysicalPosition<f64>,
+    /// Last position of the dragging pointer.
+    drag_position: PhysicalPosition<f64>,
+    /// Current scroll offset.
+    offset: PhysicalPosition<f64>,
+    /// Offset at the last call to [`KineticScroller::advance`].
+    reported: PhysicalPosition<f64>,
+    /// Minimum offset.
+    min: PhysicalPosition<f64>,
+    /// Maximum offset.
+    max: PhysicalPosition<f64>,
+    /// Velocity of the offset, in physical pixels per second.
+    velocity: PhysicalPosition<f64>,
+    /// Simulation time.
+    time: Option<u64>,
+    /// Scale factor of the latest event.
+    scale_factor: f64,
+}
+
+impl Default for KineticScroller {
+    fn default() -> Self {
+        Self::new(KineticScrollConfig::default())
+    }
+}
+
+impl KineticScroller {
+    /// Create a scroller with the given configuration.
+    pub fn new(config: KineticScrollConfig) -> Self {
+        Self {
+            config,
+            phase: Phase::Idle,
+            tracker: VelocityTracker::new(VelocityTrackerConfig::default()),
+            input: PhysicalPosition::default(),
+            drag_position: PhysicalPosition::default(),
+            offset: PhysicalPosition::default(),
+            reported: PhysicalPosition::default(),
+            min: PhysicalPosition::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: PhysicalPosition::new(f64::INFINITY, f64::INFINITY),
+            velocity: PhysicalPosition::default(),
+            time: None,
+            scale_factor: 1.0,
+        }
+    }
+
+    /// The configuration.
+    pub fn config(&self) -> &KineticScrollConfig {
+        &self.config
+    }
+
+    /// Set the range of the scroll offset, in physical pixels.
+    ///
+    /// Scrolling past these bounds meets resistance, and springs back.
+    /// By default, the offset is unbounded.
+    pub fn set_bounds(&mut self, min: PhysicalPosition<f64>, max: PhysicalPosition<f64>) {
+        self.min = min;
+        self.max = max;
+    }
+
+    /// The current scroll offset, in physical pixels.
+    ///
+    /// This includes motion not yet returned by [`KineticScroller::advance`].
+    pub fn offset(&self) -> PhysicalPosition<f64> {
+        self.offset
+    }
+
+    /// Set the scroll offset, in physical pixels, without producing a delta.
+    ///
+    /// Use this when the content is scrolled by other means.
+    pub fn set_offset(&mut self, offset: PhysicalPosition<f64>) {
+        self.offset = offset;
+        self.reported = offset;
+    }
+
+    /// The velocity of an ongoing fling, in physical pixels per second.
+    pub fn velocity(&self) -> PhysicalPosition<f64> {
+        self.velocity
+    }
+
+    /// Return `true` if the scroller will produce more deltas without new events.
+    pub fn is_animating(&self) -> bool {
+        match self.phase {
+            Phase::Idle | Phase::Momentum => {
+                self.velocity != PhysicalPosition::default() || self.out_of_bounds()
+            }
+            Phase::Scrolling(_) => true,
+            Phase::Dragging(_) => false,
+        }
+    }
+
+    /// Stop any fling immediately, leaving overscroll to spring back.
+    pub fn stop(&mut self) {
+        self.velocity = PhysicalPosition::default();
+    }
+
+    /// Update the scroller with an event.
+    ///
+    /// Touch pointers are dragged; the first touch down starts a drag, and
+    /// other touches are ignored until it ends.
+    pub fn process_pointer_event(&mut self, event: &PointerEvent) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent { pointer, state, .. }) => {
+                self.stop();
+                if pointer.pointer_type == PointerType::Touch
+                    && !matches!(self.phase, Phase::Dragging(_))
+                {
+                    self.phase = Phase::Dragging(pointer.pointer_id);
+                    self.scale_factor = state.scale_factor;
+                    self.drag_position = state.position;
+                    self.tracker.reset();
+                    self.tracker.add_sample(state.time, self.input);
+                } else if matches!(self.phase, Phase::Scrolling(_)) {
+                    self.phase = Phase::Idle;
+                }
+            }
+            PointerEvent::Move(PointerUpdate {
+                pointer,
+                current,
+                coalesced,
+                ..
+            }) if self.phase == Phase::Dragging(pointer.pointer_id) => {
+                for state in coalesced.iter().chain(core::iter::once(current)) {
+                    self.scale_factor = state.scale_factor;
+                    let delta = PhysicalPosition::new(
+                        self.drag_position.x - state.position.x,
+                        self.drag_position.y - state.position.y,
+                    );
+                    self.drag_position = state.position;
+                    self.scroll_directly(state.time, delta);
+                }
+            }
+            PointerEvent::Up(PointerButtonEvent { pointer, state, .. })
+                if self.phase == Phase::Dragging(pointer.pointer_id) =>
+            {
+                let delta = PhysicalPosition::new(
+                    self.drag_position.x - state.position.x,
+                    self.drag_position.y - state.position.y,
+                );
+                self.scroll_directly(state.time, delta);
+                self.fling(state.time);
+            }
+            PointerEvent::Cancel(pointer) if self.phase == Phase::Dragging(pointer.pointer_id) => {
+                // Leave any overscroll to spring back.
+                self.phase = Phase::Idle;
+            }
+            PointerEvent::Scroll(PointerScrollEvent {
+                delta,
+                phase,
+                state,
+                ..
+            }) => {
+                // Platform momentum is replaced by our own.
+                if matches!(self.phase, Phase::Dragging(_)) || *phase == ScrollPhase::Momentum {
+                    return;
+                }
+                if !matches!(self.phase, Phase::Scrolling(_)) {
+                    self.stop();
+                    self.tracker.reset();
+                }
+                self.scale_factor = state.scale_factor;
+                let delta = self
+                    .config
+                    .scroll_policy
+                    .to_pixel_delta(*delta, state.scale_factor);
+                self.phase = Phase::Scrolling(state.time);
+                self.scroll_directly(state.time, delta);
+                match phase {
+                    ScrollPhase::Ended => self.fling(state.time),
+                    // Leave any overscroll to spring back.
+                    ScrollPhase::Cancelled => self.phase = Phase::Idle,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Advance the scroller to `time`, and return the scroll since the last call.
+    ///
+    /// `time` is in the same time base as [`PointerState::time`], and should
+    /// be the time of the frame being prepared. The simulation advances by
+    /// at most one second per call.
+    ///
+    /// Returns `None` if the offset did not change.
+    ///
+    /// [`PointerState::time`]: ui_events::pointer::PointerState::time
+    pub fn advance(&mut self, time: u64) -> Option<ScrollDelta> {
+        if let Phase::Scrolling(last) = self.phase {
+            if time.saturating_sub(last) >= self.config.scroll_end_timeout {
+                self.fling(last);
+            }
+        }
+
+        let sim_time = *self.time.get_or_insert(time);
+        if matches!(self.phase, Phase::Idle | Phase::Momentum) && self.is_animating() {
+            let steps = (time.saturating_sub(sim_time) / STEP).min(MAX_STEPS);
+            for _ in 0..steps {
+                self.step();
+            }
+            // Time beyond the step limit is skipped.
+            self.time = Some(if steps == MAX_STEPS {
+                time
+            } else {
+                sim_time + steps * STEP
+            });
+            if !self.is_animating() {
+                self.phase = Phase::Idle;
+            }
+        } else {
+            self.time = Some(sim_time.max(time));
+        }
+
+        let delta = PhysicalPosition::new(
+            self.offset.x - self.reported.x,
+            self.offset.y - self.reported.y,
+        );
+        self.reported = self.offset;
+        (delta != PhysicalPosition::default()).then_some(ScrollDelta::PixelDelta(delta))
+    }
+
+    /// Apply direct input at `time`, with resistance past the bounds.
+    fn scroll_directly(&mut self, time: u64, delta: PhysicalPosition<f64>) {
+        let r = self.config.overscroll_resistance;
+        self.offset.x = resist(self.offset.x, delta.x, self.min.x, self.max.x, r);
+        self.offset.y = resist(self.offset.y, delta.y, self.min.y, self.max.y, r);
+        self.input.x += delta.x;
+        self.input.y += delta.y;
+        self.tracker.add_sample(time, self.input);
+    }
+
+    /// End direct input at `time`, starting a fling if it was fast enough.
+    fn fling(&mut self, time: u64) {
+        self.phase = Phase::Momentum;
+        self.time = Some(self.time.map_or(time, |t| t.max(time)));
+        let Some(estimate) = self.tracker.estimate() else {
+            return;
+        };
+        let v = estimate.velocity;
+        let speed = crate::math::hypot(v.x, v.y);
+        if speed < self.config.min_fling_velocity * self.scale_factor {
+            return;
+        }
+        let max = self.config.max_fling_velocity * self.scale_factor;
+        let scale = if speed > max { max / speed } else { 1.0 };
+        self.velocity = PhysicalPosition::new(v.x * scale, v.y * scale);
+    }
+
+    /// Advance the simulation by one step.
+    fn step(&mut self) {
+        let stop = self.config.stop_velocity * self.scale_factor;
+        let (x, vx) = self.step_axis(self.offset.x, self.velocity.x, self.min.x, self.max.x, stop);
+        let (y, vy) = self.step_axis(self.offset.y, self.velocity.y, self.min.y, self.max.y, stop);
+        self.offset = PhysicalPosition::new(x, y);
+        self.velocity = PhysicalPosition::new(vx, vy);
+    }
+
+    fn step_axis(&self, x: f64, v: f64, min: f64, max: f64, stop: f64) -> (f64, f64) {
+        let overshoot = if x < min {
+            x - min
+        } else if x > max {
+            x - max
+        } else {
+            0.0
+        };
+        if overshoot == 0.0 {
+            if v == 0.0 {
+                return (x, v);
+            }
+            let v = v - self.config.friction * v * STEP_SECS;
+            if v.abs() < stop {
+                return (x, 0.0);
+            }
+            return (x + v * STEP_SECS, v);
+        }
+
+        let bound = x - overshoot;
+        let a = -self.config.overscroll_stiffness * overshoot - self.config.overscroll_damping * v;
+        let v = v + a * STEP_SECS;
+        let next = x + v * STEP_SECS;
+        // Settle on the bound when returning to it, or when close and slow.
+        if (next - bound) * overshoot <= 0.0 || (overshoot.abs() < 0.5 && v.abs() < stop) {
+            return (bound, 0.0);
+        }
+        (next, v)
+    }
+
+    fn out_of_bounds(&self) -> bool {
+        self.offset.x < self.min.x
+            || self.offset.x > self.max.x
+            || self.offset.y < self.min.y
+            || self.offset.y > self.max.y
+    }
+}
+
+/// Move `x` by `delta`, scaling the part beyond `[min, max]` by `resistance`.
+fn resist(x: f64, delta: f64, min: f64, max: f64, resistance: f64) -> f64 {
+    let target = x + delta;
+    if delta > 0.0 && target > max {
+        let start = x.max(max);
+        start + (target - start) * resistance
+    } else if delta < 0.0 && target < min {
+        let start = x.min(min);
+        start + (target - start) * resistance
+    } else {
+        target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::vec;
+    use ui_events::ScrollSource;
+    use ui_events::pointer::{PointerButton, PointerInfo, PointerState};
+
+    const MS: u64 = 1_000_000;
+
+    fn info(id: u64, pointer_type: PointerType) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type,
+        }
+    }
+
+    fn state(time: u64, x: f64, y: f64) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y },
+            ..Default::default()
+        }
+    }
+
+    fn down(time: u64, y: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(2, PointerType::Touch),
+            state: state(time, 0.0, y),
+        })
+    }
+
+    fn move_to(time: u64, y: f64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer: info(2, PointerType::Touch),
+            current: state(time, 0.0, y),
+            coalesced: vec![],
+            predicted: vec![],
+        })
+    }
+
+    fn up(time: u64, y: f64) -> PointerEvent {
+        PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(2, PointerType::Touch),
+            state: state(time, 0.0, y),
+        })
+    }
+
+    fn wheel(time: u64, lines: f32) -> PointerEvent {
+        PointerEvent::Scroll(PointerScrollEvent {
+            pointer: info(1, PointerType::Mouse),
+            delta: ScrollDelta::LineDelta(0.0, lines),
+            phase: ScrollPhase::Changed,
+            source: ScrollSource::Wheel,
+            state: state(time, 0.0, 0.0),
+        })
+    }
+
+    fn touchpad(time: u64, y: f64, phase: ScrollPhase) -> PointerEvent {
+        PointerEvent::Scroll(PointerScrollEvent {
+            pointer: info(1, PointerType::Mouse),
+            delta: ScrollDelta::PixelDelta(PhysicalPosition { x: 0.0, y }),
+            phase,
+            source: ScrollSource::Touchpad,
+            state: state(time, 0.0, 0.0),
+        })
+    }
+
+    fn dy(delta: Option<ScrollDelta>) -> f64 {
+        match delta {
+            Some(ScrollDelta::PixelDelta(p)) => p.y,
+            None => 0.0,
+            Some(d) => panic!("unexpected {d:?}"),
+        }
+    }
+
+    /// Run frames every 16ms from `start` until the scroller stops, returning
+    /// the total scroll and the time it stopped.
+    fn run(s: &mut KineticScroller, start: u64) -> (f64, u64) {
+        let mut total = 0.0;
+        let mut t = start;
+        while s.is_animating() {
+            t += 16 * MS;
+            total += dy(s.advance(t));
+            assert!(t < start + 10_000 * MS, "did not settle");
+        }
+        (total, t)
+    }
+
+    #[test]
+    fn drag_scrolls_directly_and_flings() {
+        let mut s = KineticScroller::default();
+        s.process_pointer_event(&down(0, 500.0));
+        s.process_pointer_event(&move_to(10 * MS, 480.0));
+        assert_eq!(dy(s.advance(16 * MS)), 20.0);
+        for i in 2..=5 {
+            s.process_pointer_event(&move_to(i * 10 * MS, 500.0 - 20.0 * i as f64));
+        }
+        s.process_pointer_event(&up(60 * MS, 380.0));
+        assert_eq!(dy(s.advance(60 * MS)), 100.0);
+
+        // 2000 px/s upward finger motion becomes a downward fling.
+        assert!((s.velocity().y - 2_000.0).abs() < 1e-6);
+        let (total, _) = run(&mut s, 60 * MS);
+        // Close to v / friction, less the tail below the stop velocity.
+        assert!((total - 1_000.0).abs() < 10.0, "flung {total}");
+        assert!(!s.is_animating());
+    }
+
+    #[test]
+    fn slow_release_does_not_fling() {
+        let mut s = KineticScroller::default();
+        s.process_pointer_event(&down(0, 500.0));
+        s.process_pointer_event(&move_to(100 * MS, 400.0));
+        s.process_pointer_event(&move_to(300 * MS, 400.0));
+        s.process_pointer_event(&up(310 * MS, 400.0));
+        assert_eq!(dy(s.advance(320 * MS)), 100.0);
+        assert!(!s.is_animating());
+        assert_eq!(s.advance(400 * MS), None);
+    }
+
+    #[test]
+    fn down_cancels_fling() {
+        let mut s = KineticScroller::default();
+        s.process_pointer_event(&down(0, 500.0));
+        s.process_pointer_event(&move_to(10 * MS, 480.0));
+        s.process_pointer_event(&up(20 * MS, 460.0));
+        s.advance(20 * MS);
+        assert!(s.is_animating());
+        s.advance(100 * MS);
+        s.process_pointer_event(&down(110 * MS, 300.0));
+        assert!(!s.is_animating());
+        assert_eq!(s.velocity(), PhysicalPosition::default());
+        assert_eq!(s.advance(200 * MS), None);
+    }
+
+    #[test]
+    fn line_wheel_stream_flings_after_it_stops() {
+        let mut s = KineticScroller::default();
+        // Isolated clicks scroll by a line and stop.
+        s.process_pointer_event(&wheel(0, 1.0));
+        assert_eq!(dy(s.advance(16 * MS)), 40.0);
+        assert_eq!(dy(s.advance(100 * MS)), 0.0);
+        assert!(!s.is_animating());
+
+        // A fast stream keeps going.
+        for i in 0..5 {
+            s.process_pointer_event(&wheel(1_000 * MS + i * 10 * MS, 1.0));
+        }
+        assert_eq!(dy(s.advance(1_048 * MS)), 200.0);
+        assert!(s.is_animating());
+        assert_eq!(s.velocity(), PhysicalPosition::default());
+        // Flings once the stream pauses.
+        let (total, _) = run(&mut s, 1_048 * MS);
+        assert!(total > 1_000.0, "flung {total}");
+    }
+
+    #[test]
+    fn touchpad_flings_at_end_of_gesture() {
+        let mut s = KineticScroller::default();
+        s.process_pointer_event(&touchpad(0, 0.0, ScrollPhase::Started));
+        for i in 1..=4 {
+            s.process_pointer_event(&touchpad(i * 10 * MS, 10.0, ScrollPhase::Changed));
+        }
+        s.process_pointer_event(&touchpad(50 * MS, 10.0, ScrollPhase::Ended));
+        assert!((s.velocity().y - 1_000.0).abs() < 1e-6);
+        assert_eq!(dy(s.advance(50 * MS)), 50.0);
+        // The platform's own momentum does not add to ours.
+        s.process_pointer_event(&touchpad(50 * MS, 10.0, ScrollPhase::Momentum));
+        assert!((s.velocity().y - 1_000.0).abs() < 1e-6);
+        let d = dy(s.advance(60 * MS));
+        assert!((d - 10.0).abs() < 0.5, "scrolled {d}");
+    }
+
+    #[test]
+    fn cancelled_touchpad_gesture_does_not_fling() {
+        let mut s = KineticScroller::default();
+        s.process_pointer_event(&touchpad(0, 0.0, ScrollPhase::Started));
+        for i in 1..=4 {
+            s.process_pointer_event(&touchpad(i * 10 * MS, 10.0, ScrollPhase::Changed));
+        }
+        s.process_pointer_event(&touchpad(50 * MS, 0.0, ScrollPhase::Cancelled));
+        assert_eq!(s.velocity(), PhysicalPosition::default());
+        assert_eq!(dy(s.advance(50 * MS)), 40.0);
+        assert!(!s.is_animating());
+        // Nor after the end of gesture timeout.
+        assert_eq!(s.advance(1_000 * MS), None);
+    }
+
+    #[test]
+    fn overscroll_resists_and_bounces_back() {
+        let mut s = KineticScroller::default();
+        s.set_bounds(
+            PhysicalPosition::new(0.0, 0.0),
+            PhysicalPosition::new(0.0, 100.0),
+        );
+        s.process_pointer_event(&down(0, 500.0));
+        s.process_pointer_event(&move_to(100 * MS, 300.0));
+        // 100px in bounds, then 100px at half strength.
+        assert_eq!(dy(s.advance(100 * MS)), 150.0);
+        s.process_pointer_event(&move_to(300 * MS, 300.0));
+        s.process_pointer_event(&up(310 * MS, 300.0));
+        assert!(s.is_animating());
+        let (total, _) = run(&mut s, 310 * MS);
+        assert!((total + 50.0).abs() < 1e-9, "returned {total}");
+        assert_eq!(s.offset().y, 100.0);
+
+        // A fling past the end bounces back to it.
+        let mut s = KineticScroller::default();
+        s.set_bounds(
+            PhysicalPosition::new(0.0, 0.0),
+            PhysicalPosition::new(0.0, 100.0),
+        );
+        s.process_pointer_event(&down(0, 500.0));
+        s.process_pointer_event(&move_to(10 * MS, 480.0));
+        s.process_pointer_event(&move_to(20 * MS, 460.0));
+        s.process_pointer_event(&up(30 * MS, 440.0));
+        let mut furthest: f64 = 0.0;
+        let mut t = 30 * MS;
+        while s.is_animating() {
+            t += 16 * MS;
+            s.advance(t);
+            furthest = furthest.max(s.offset().y);
+        }
+        assert!(furthest > 100.0);
+        assert_eq!(s.offset().y, 100.0);
+    }
+
+    #[test]
+    fn deterministic_across_frame_rates() {
+        let fling = |frame: u64| {
+            let mut s = KineticScroller::default();
+            s.process_pointer_event(&down(0, 500.0));
+            s.process_pointer_event(&move_to(10 * MS, 480.0));
+            s.process_pointer_event(&up(20 * MS, 460.0));
+            let mut t = 20 * MS;
+            let mut total = 0.0;
+            while t < 3_000 * MS {
+                t += frame;
+                total += dy(s.advance(t));
+            }
+            total
+        };
+        assert!((fling(8 * MS) - fling(16 * MS)).abs() < 1e-9);
+    }
+}
@@ -13,13 +13,24 @@
 //!
 //! - [`PrimaryPointerState`]: current pointer state, coalesced and predicted motion,
 //!   per-frame button transitions, and helpers for motion in physical/logical units.
+//!   Its [`ScrollState`] accumulates the frame's scrolling, resolving lines and pages to
+//!   pixels with a [`ScrollPolicy`], and its [`PointerGestureState`] compounds touchpad
+//!   pinch and rotation gestures.
+//! - [`PointerStates`]: the same per-pointer information for every active pointer,
+//!   keyed by [`PointerId`](ui_events::pointer::PointerId), for multi-touch and mixed devices.
 //! - [`KeyboardState`]: current modifiers, keys down, and per-frame key transitions.
-//! - [`InputState`]: a convenience container bundling both states and a per-frame clear.
+//! - [`GestureRecognizer`]: taps, long presses, drags, flings, and two-finger pinch
+//!   and rotate, recognized from the pointer event stream.
+//! - [`VelocityTracker`]: least-squares velocity and acceleration estimates for a pointer,
+//!   for flings and kinetic scrolling.
+//! - [`KineticScroller`]: momentum scrolling with friction and overscroll bounce, synthesized
+//!   from scroll events and touch drags the same way on every backend.
+//! - [`InputState`]: a convenience container bundling these states and a per-frame clear.
 //!
 //! ## Typical lifecycle per frame:
 //!
 //! 1. Receive backend events and convert them to `ui-events` types.
-//! 2. Update `PrimaryPointerState` and `KeyboardState` with the events.
+//! 2. Update `PrimaryPointerState`, `PointerStates` and `KeyboardState` with the events.
 //! 3. Read state during your UI update (e.g. check just pressed, motion, etc.).
 //! 4. Call [`InputState::clear_frame`] before the next frame.
 //!
@@ -34,6 +45,7 @@
 //!
 //! // 1-2) In your event loop, feed events into state
 //! fn on_pointer_event(input: &mut InputState, e: PointerEvent) {
+//!     input.pointers.process_pointer_event(e.clone());
 //!     input.primary_pointer.process_pointer_event(e);
 //! }
 //! fn on_keyboard_event(input: &mut InputState, e: KeyboardEvent) {
@@ -63,7 +75,8 @@
 //! ## Features
 //!
 //! - `std` (enabled by default): Use the Rust standard library.
-//! - `libm`: Enable `ui-events/libm` transitively for `no_std` environments.
+//! - `libm`: Use `libm` for floating point math in `no_std` environments,
+//!   and enable `ui-events/libm` transitively.
 // LINEBENDER LINT SET - lib.rs - v3
 // See https://linebender.org/wiki/canonical-lints/
 // These lints shouldn't apply to examples or tests.
@@ -75,12 +88,32 @@
 // END LINEBENDER LINT SET
 #![no_std]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("ui-input-state requires either the `std` or `libm` feature");
+
+// `libm` is only used when `std` is unavailable.
+#[cfg(all(feature = "std", feature = "libm"))]
+use libm as _;
+
 extern crate alloc;
 
+mod gesture_recognizer;
 mod input_state;
 mod keyboard_state;
+mod kinetic_scroll;
+mod math;
+mod pointer_gesture_state;
+mod pointer_states;
 mod primary_pointer_state;
+mod scroll_state;
+mod velocity_tracker;
 
+pub use crate::gesture_recognizer::{Gesture, GestureConfig, GestureRecognizer};
 pub use crate::input_state::InputState;
 pub use crate::keyboard_state::KeyboardState;
+pub use crate::kinetic_scroll::{KineticScrollConfig, KineticScroller};
+pub use crate::pointer_gesture_state::PointerGestureState;
+pub use crate::pointer_states::{PointerStates, TrackedPointer};
 pub use crate::primary_pointer_state::PrimaryPointerState;
+pub use crate::scroll_state::{ScrollPolicy, ScrollState};
+pub use crate::velocity_tracker::{VelocityEstimate, VelocityTracker, VelocityTrackerConfig};
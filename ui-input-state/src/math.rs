@@ -0,0 +1,24 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Floating point functions which need either `std` or `libm`.
+
+#[cfg(feature = "std")]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    x.hypot(y)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    libm::hypot(x, y)
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+    libm::atan2(y, x)
+}
@@ -0,0 +1,145 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Per-frame touchpad gesture accumulation.
+//!
+//! [`PointerGestureState`] compounds the [`PointerGestureEvent`]s of a frame
+//! into a single pinch scale factor and a total rotation.
+//! [`PrimaryPointerState`] keeps one for the primary pointer.
+//!
+//! [`PrimaryPointerState`]: crate::PrimaryPointerState
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::PrimaryPointerState;
+//! use ui_events::pointer::PointerEvent;
+//!
+//! let mut ps = PrimaryPointerState::default();
+//! let mut zoom = 1.0_f32;
+//! let mut angle = 0.0_f32;
+//! # let events: Vec<PointerEvent> = unimplemented!();
+//! for e in events {
+//!     ps.process_pointer_event(e);
+//! }
+//! zoom *= ps.gesture().pinch_scale();
+//! angle += ps.gesture().rotation();
+//! ps.clear_frame();
+//! ```
+
+use ui_events::pointer::{PointerGesture, PointerGestureEvent};
+
+/// Touchpad gestures accumulated over a frame.
+#[derive(Clone, Debug)]
+pub struct PointerGestureState {
+    /// Product of `1 + delta` for the pinches this frame.
+    pinch_scale: f32,
+    /// Sum of the rotations this frame, clockwise in radians.
+    rotation: f32,
+    /// Number of gesture events this frame.
+    events: usize,
+    /// A gesture event arrived this frame or the previous one.
+    in_progress: bool,
+}
+
+impl Default for PointerGestureState {
+    fn default() -> Self {
+        Self {
+            pinch_scale: 1.0,
+            rotation: 0.0,
+            events: 0,
+            in_progress: false,
+        }
+    }
+}
+
+impl PointerGestureState {
+    /// Return `true` if there was any gesture event this frame.
+    pub fn is_gesturing(&self) -> bool {
+        self.events != 0
+    }
+
+    /// Return `true` if a gesture is in progress.
+    ///
+    /// Gesture events do not mark where a gesture begins and ends, so a
+    /// gesture is considered to be in progress from its first event until a
+    /// whole frame passes without any.
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+
+    /// Scale factor from the pinches this frame.
+    ///
+    /// This is the product of `1 + delta` for each [`PointerGesture::Pinch`],
+    /// so multiply your current scale by it. It is 1 without pinches.
+    pub fn pinch_scale(&self) -> f32 {
+        self.pinch_scale
+    }
+
+    /// Total clockwise rotation this frame, in radians.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Add a gesture event to the totals.
+    pub fn process_gesture_event(&mut self, event: &PointerGestureEvent) {
+        match event.gesture {
+            PointerGesture::Pinch(delta) => self.pinch_scale *= 1.0 + delta,
+            PointerGesture::Rotate(delta) => self.rotation += delta,
+        }
+        self.events += 1;
+        self.in_progress = true;
+    }
+
+    /// Clear the per-frame totals to prepare for a new frame.
+    pub fn clear_frame(&mut self) {
+        *self = Self {
+            in_progress: self.events != 0,
+            ..Default::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ui_events::pointer::{PointerId, PointerInfo, PointerState, PointerType};
+
+    fn gesture(gesture: PointerGesture) -> PointerGestureEvent {
+        PointerGestureEvent {
+            pointer: PointerInfo {
+                pointer_id: Some(PointerId::PRIMARY),
+                persistent_device_id: None,
+                pointer_type: PointerType::Mouse,
+            },
+            gesture,
+            state: PointerState::default(),
+        }
+    }
+
+    #[test]
+    fn compounds_pinches_and_sums_rotation() {
+        let mut g = PointerGestureState::default();
+        assert_eq!(g.pinch_scale(), 1.0);
+        assert!(!g.is_gesturing());
+
+        g.process_gesture_event(&gesture(PointerGesture::Pinch(1.0)));
+        g.process_gesture_event(&gesture(PointerGesture::Pinch(-0.25)));
+        g.process_gesture_event(&gesture(PointerGesture::Rotate(0.5)));
+        g.process_gesture_event(&gesture(PointerGesture::Rotate(-0.25)));
+        assert_eq!(g.pinch_scale(), 1.5);
+        assert_eq!(g.rotation(), 0.25);
+        assert!(g.is_gesturing());
+        assert!(g.is_in_progress());
+
+        // Still in progress for a frame without events.
+        g.clear_frame();
+        assert_eq!(g.pinch_scale(), 1.0);
+        assert_eq!(g.rotation(), 0.0);
+        assert!(!g.is_gesturing());
+        assert!(g.is_in_progress());
+
+        g.clear_frame();
+        assert!(!g.is_in_progress());
+    }
+}
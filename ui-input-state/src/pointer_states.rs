@@ -0,0 +1,639 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # State of every active pointer across frames.
+//!
+//! `PointerStates` tracks each pointer by its [`PointerId`], so multi-touch
+//! and mixed pen/mouse input can be followed, unlike [`PrimaryPointerState`]
+//! which only follows the primary pointer.
+//!
+//! Pointers become active on [`PointerEvent::Down`] or [`PointerEvent::Enter`]
+//! (or on a [`PointerEvent::Move`] from a pointer that is not yet tracked),
+//! and are retired on [`PointerEvent::Cancel`] and [`PointerEvent::Leave`].
+//! They are also retired on [`PointerEvent::Up`] once no buttons remain
+//! pressed, unless they entered the area and can keep hovering, like a mouse.
+//! Retired pointers are kept until [`PointerStates::clear_frame`], so that
+//! every pointer in [`PointerStates::just_pressed`] and
+//! [`PointerStates::just_released`] can still be looked up with
+//! [`PointerStates::get`], even a touch that went down and up in one frame.
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::PointerStates;
+//! use ui_events::pointer::PointerEvent;
+//!
+//! let mut pointers = PointerStates::default();
+//! # let events: Vec<PointerEvent> = vec![];
+//! for e in events {
+//!     pointers.process_pointer_event(e);
+//! }
+//! for id in pointers.just_pressed() {
+//!     let p = pointers.get(*id).unwrap();
+//!     let _ = p.current_position();
+//! }
+//! let _touching = pointers.iter().filter(|p| p.is_pressed()).count();
+//! pointers.clear_frame();
+//! ```
+//!
+//! [`PrimaryPointerState`]: crate::PrimaryPointerState
+extern crate alloc;
+use alloc::vec::Vec;
+
+use ui_events::pointer::{
+    PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerId, PointerInfo,
+    PointerState, PointerUpdate,
+};
+
+use dpi::{LogicalPosition, PhysicalPosition};
+
+/// A stateful view of a single tracked pointer.
+#[derive(Clone, Debug)]
+pub struct TrackedPointer {
+    /// Identifying information about the pointer.
+    pointer: PointerInfo,
+    /// Buttons that were pressed during the current frame.
+    just_pressed: PointerButtons,
+    /// Buttons that were released during the current frame.
+    just_released: PointerButtons,
+    /// `true` between a `Down` and the `Up` releasing the last button.
+    pressed: bool,
+    /// `true` if the pointer entered the area, and may hover when not pressed.
+    hovering: bool,
+    /// `false` until an event carrying a [`PointerState`] is received.
+    known: bool,
+    /// `false` once retired, until the end of the frame.
+    active: bool,
+    /// Current state.
+    current: PointerState,
+    /// Coalesced states, ordered by `time`.
+    coalesced: Vec<PointerState>,
+    /// Predicted states, ordered by `time`.
+    predicted: Vec<PointerState>,
+}
+
+impl TrackedPointer {
+    fn new(pointer: PointerInfo) -> Self {
+        Self {
+            pointer,
+            just_pressed: PointerButtons::default(),
+            just_released: PointerButtons::default(),
+            pressed: false,
+            hovering: false,
+            known: false,
+            active: true,
+            current: PointerState::default(),
+            coalesced: Vec::new(),
+            predicted: Vec::new(),
+        }
+    }
+
+    /// Identifying information about the pointer.
+    pub fn pointer(&self) -> &PointerInfo {
+        &self.pointer
+    }
+
+    /// The id of the pointer.
+    pub fn pointer_id(&self) -> PointerId {
+        self.pointer
+            .pointer_id
+            .expect("only pointers with an id are tracked")
+    }
+
+    /// Current state.
+    pub fn current(&self) -> &PointerState {
+        &self.current
+    }
+
+    /// Coalesced states this frame, ordered by `time`.
+    pub fn coalesced(&self) -> &[PointerState] {
+        &self.coalesced
+    }
+
+    /// Predicted states, ordered by `time`.
+    pub fn predicted(&self) -> &[PointerState] {
+        &self.predicted
+    }
+
+    /// Return `true` if the `button` was pressed within the last frame.
+    pub fn is_just_pressed(&self, button: PointerButton) -> bool {
+        self.just_pressed.contains(button)
+    }
+
+    /// Return `true` if the `button` was released within the last frame.
+    pub fn is_just_released(&self, button: PointerButton) -> bool {
+        self.just_released.contains(button)
+    }
+
+    /// Return `true` if the specified `button` is currently held down.
+    pub fn is_down(&self, button: PointerButton) -> bool {
+        self.current.buttons.contains(button)
+    }
+
+    /// Return `true` if any button is currently held down.
+    pub fn is_any_down(&self) -> bool {
+        !self.current.buttons.is_empty()
+    }
+
+    /// Return `true` if the pointer is active, rather than retired within the last frame.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Return `true` if the pointer is pressed: a touch or pen in contact,
+    /// or a mouse with a button held.
+    ///
+    /// Unlike [`is_any_down`](Self::is_any_down), this does not depend on
+    /// the platform reporting a button for touch contacts.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Current position.
+    pub fn current_position(&self) -> PhysicalPosition<f64> {
+        self.current.position
+    }
+
+    /// Current position (in logical units).
+    pub fn current_logical_position(&self) -> LogicalPosition<f64> {
+        self.current.logical_position()
+    }
+
+    /// Relative motion this frame.
+    pub fn motion(&self) -> PhysicalPosition<f64> {
+        let current = self.current.position;
+        let first = self
+            .coalesced
+            .first()
+            .map(|s| s.position)
+            .unwrap_or(current);
+        PhysicalPosition {
+            x: current.x - first.x,
+            y: current.y - first.y,
+        }
+    }
+
+    /// Relative motion this frame (in logical units).
+    pub fn logical_motion(&self) -> LogicalPosition<f64> {
+        let current = self.current.logical_position();
+        let first = self
+            .coalesced
+            .first()
+            .map(|s| s.logical_position())
+            .unwrap_or(current);
+        LogicalPosition {
+            x: current.x - first.x,
+            y: current.y - first.y,
+        }
+    }
+
+    /// Replace the current state, coalescing the previous one if it is known.
+    fn set_current(&mut self, state: PointerState) {
+        let previous = core::mem::replace(&mut self.current, state);
+        if self.known {
+            self.coalesced.push(previous);
+        }
+        self.known = true;
+    }
+
+    /// Stop tracking the pointer, keeping its state until the end of the frame.
+    fn retire(&mut self) {
+        self.active = false;
+        self.pressed = false;
+        self.predicted.clear();
+    }
+
+    fn clear_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.coalesced.clear();
+        self.predicted.clear();
+    }
+}
+
+/// A stateful view of all active pointers.
+#[derive(Clone, Debug, Default)]
+pub struct PointerStates {
+    /// Active pointers, in the order they became active, and pointers
+    /// retired within the current frame.
+    pointers: Vec<TrackedPointer>,
+    /// Pointers that received a `Down` during the current frame.
+    just_pressed: Vec<PointerId>,
+    /// Pointers that received an `Up` during the current frame.
+    just_released: Vec<PointerId>,
+}
+
+impl PointerStates {
+    /// The pointer with `id`, if it is active or was retired within the last frame.
+    ///
+    /// See [`TrackedPointer::is_active`].
+    pub fn get(&self, id: PointerId) -> Option<&TrackedPointer> {
+        self.pointers.iter().find(|p| p.pointer_id() == id)
+    }
+
+    /// The primary pointer, if it is active or was retired within the last frame.
+    pub fn primary(&self) -> Option<&TrackedPointer> {
+        self.get(PointerId::PRIMARY)
+    }
+
+    /// Iterate over the active pointers, in the order they became active.
+    pub fn iter(&self) -> impl Iterator<Item = &TrackedPointer> {
+        self.pointers.iter().filter(|p| p.active)
+    }
+
+    /// Number of active pointers.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Return `true` if no pointers are active.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Return `true` if the pointer with `id` is active.
+    pub fn is_active(&self, id: PointerId) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Number of active pointers which are pressed.
+    ///
+    /// See [`TrackedPointer::is_pressed`].
+    pub fn pressed_count(&self) -> usize {
+        self.iter().filter(|p| p.pressed).count()
+    }
+
+    /// Pointers that received a [`PointerEvent::Down`] within the last frame.
+    pub fn just_pressed(&self) -> &[PointerId] {
+        &self.just_pressed
+    }
+
+    /// Pointers that received a [`PointerEvent::Up`] within the last frame.
+    ///
+    /// These may have been retired, but can still be looked up with [`Self::get`].
+    pub fn just_released(&self) -> &[PointerId] {
+        &self.just_released
+    }
+
+    /// Return `true` if the pointer with `id` was pressed within the last frame.
+    pub fn is_just_pressed(&self, id: PointerId) -> bool {
+        self.just_pressed.contains(&id)
+    }
+
+    /// Return `true` if the pointer with `id` was released within the last frame.
+    pub fn is_just_released(&self, id: PointerId) -> bool {
+        self.just_released.contains(&id)
+    }
+
+    /// Clear the per-frame state to prepare for a new frame.
+    pub fn clear_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.pointers.retain(|p| p.active);
+        for p in &mut self.pointers {
+            p.clear_frame();
+        }
+    }
+
+    /// Index of the active pointer with `id`.
+    fn position(&self, id: PointerId) -> Option<usize> {
+        self.pointers
+            .iter()
+            .position(|p| p.active && p.pointer_id() == id)
+    }
+
+    /// Find the active pointer for `info`, or start tracking it.
+    ///
+    /// A pointer retired within the current frame is replaced, keeping the
+    /// buttons it pressed and released this frame.
+    /// Returns `None` for events without a pointer id.
+    fn get_or_insert(&mut self, info: PointerInfo) -> Option<&mut TrackedPointer> {
+        let id = info.pointer_id?;
+        let idx = match self.position(id) {
+            Some(idx) => idx,
+            None => {
+                let mut p = TrackedPointer::new(info);
+                if let Some(retired) = self.pointers.iter().position(|p| p.pointer_id() == id) {
+                    let retired = self.pointers.remove(retired);
+                    p.just_pressed = retired.just_pressed;
+                    p.just_released = retired.just_released;
+                }
+                self.pointers.push(p);
+                self.pointers.len() - 1
+            }
+        };
+        Some(&mut self.pointers[idx])
+    }
+
+    fn retire(&mut self, info: &PointerInfo) {
+        if let Some(idx) = info.pointer_id.and_then(|id| self.position(id)) {
+            self.pointers[idx].retire();
+        }
+    }
+
+    /// Update the state based on the given pointer event.
+    ///
+    /// Events without a [`PointerId`] are ignored.
+    pub fn process_pointer_event(&mut self, event: PointerEvent) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button,
+                pointer,
+                state,
+            }) => {
+                let Some(id) = pointer.pointer_id else {
+                    return;
+                };
+                let p = self.get_or_insert(pointer).expect("pointer has an id");
+                if let Some(b) = button {
+                    p.just_pressed.insert(b);
+                }
+                p.pressed = true;
+                p.set_current(state);
+                p.predicted.clear();
+                if !self.just_pressed.contains(&id) {
+                    self.just_pressed.push(id);
+                }
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button,
+                pointer,
+                state,
+            }) => {
+                let Some(id) = pointer.pointer_id else {
+                    return;
+                };
+                let Some(idx) = self.position(id) else {
+                    return;
+                };
+                let p = &mut self.pointers[idx];
+                if let Some(b) = button {
+                    p.just_released.insert(b);
+                }
+                let released = state.buttons.is_empty();
+                p.set_current(state);
+                p.predicted.clear();
+                if released {
+                    p.pressed = false;
+                    if !p.hovering {
+                        p.retire();
+                    }
+                }
+                if !self.just_released.contains(&id) {
+                    self.just_released.push(id);
+                }
+            }
+            PointerEvent::Move(PointerUpdate {
+                pointer,
+                current,
+                coalesced,
+                predicted,
+            }) => {
+                let Some(p) = self.get_or_insert(pointer) else {
+                    return;
+                };
+                if !p.known && !p.pressed {
+                    // Moving without being pressed, so this pointer can hover.
+                    p.hovering = true;
+                }
+                p.set_current(current);
+                p.coalesced.extend(coalesced);
+                p.predicted.clear();
+                p.predicted.extend(predicted);
+            }
+            PointerEvent::Enter(pointer) => {
+                if let Some(p) = self.get_or_insert(pointer) {
+                    p.hovering = true;
+                }
+            }
+            PointerEvent::Cancel(pointer) | PointerEvent::Leave(pointer) => {
+                self.retire(&pointer);
+            }
+            PointerEvent::Scroll(..) | PointerEvent::Gesture(..) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use ui_events::pointer::PointerType;
+
+    fn info(id: u64, pointer_type: PointerType) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type,
+        }
+    }
+
+    fn touch(id: u64) -> PointerInfo {
+        info(id, PointerType::Touch)
+    }
+
+    fn state(time: u64, x: f64, buttons: PointerButtons) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y: 0.0 },
+            buttons,
+            ..Default::default()
+        }
+    }
+
+    fn down(pointer: PointerInfo, time: u64, x: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: None,
+            pointer,
+            state: state(time, x, PointerButtons::default()),
+        })
+    }
+
+    fn up(pointer: PointerInfo, time: u64, x: f64) -> PointerEvent {
+        PointerEvent::Up(PointerButtonEvent {
+            button: None,
+            pointer,
+            state: state(time, x, PointerButtons::default()),
+        })
+    }
+
+    fn move_to(pointer: PointerInfo, time: u64, x: f64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer,
+            current: state(time, x, PointerButtons::default()),
+            coalesced: vec![],
+            predicted: vec![state(time + 1, x + 1.0, PointerButtons::default())],
+        })
+    }
+
+    #[test]
+    fn tracks_two_touches_independently() {
+        let mut s = PointerStates::default();
+        s.process_pointer_event(down(touch(1), 1, 10.0));
+        s.process_pointer_event(down(touch(5), 2, 100.0));
+
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.pressed_count(), 2);
+        assert_eq!(
+            s.just_pressed(),
+            &[PointerId::PRIMARY, PointerId::new(5).unwrap()]
+        );
+
+        s.clear_frame();
+        s.process_pointer_event(move_to(touch(5), 3, 120.0));
+
+        let second = s.get(PointerId::new(5).unwrap()).unwrap();
+        assert_eq!(second.current_position().x, 120.0);
+        assert_eq!(second.motion().x, 20.0);
+        assert_eq!(second.predicted().len(), 1);
+        let primary = s.primary().unwrap();
+        assert_eq!(primary.current_position().x, 10.0);
+        assert_eq!(primary.motion().x, 0.0);
+        assert!(s.just_pressed().is_empty());
+    }
+
+    #[test]
+    fn touch_up_retires_pointer() {
+        let mut s = PointerStates::default();
+        s.process_pointer_event(down(touch(2), 1, 0.0));
+        s.process_pointer_event(up(touch(2), 2, 0.0));
+
+        let id = PointerId::new(2).unwrap();
+        assert!(!s.is_active(id));
+        assert!(s.is_just_pressed(id));
+        assert!(s.is_just_released(id));
+
+        s.clear_frame();
+        assert!(!s.is_just_released(id));
+        assert!(s.get(id).is_none());
+    }
+
+    #[test]
+    fn press_and_release_within_one_frame() {
+        let mut s = PointerStates::default();
+        let press = |time, buttons| {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                pointer: touch(1),
+                state: state(time, 5.0, buttons),
+            })
+        };
+        s.process_pointer_event(press(1, PointerButton::Primary.into()));
+        s.process_pointer_event(PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: touch(1),
+            state: state(2, 6.0, PointerButtons::default()),
+        }));
+        s.process_pointer_event(down(touch(2), 3, 0.0));
+
+        assert_eq!(s.len(), 1);
+        assert_eq!(s.iter().count(), 1);
+        for id in s.just_pressed() {
+            assert!(s.get(*id).is_some());
+        }
+        let p = s.primary().unwrap();
+        assert!(!p.is_active());
+        assert!(!p.is_pressed());
+        assert!(p.is_just_pressed(PointerButton::Primary));
+        assert!(p.is_just_released(PointerButton::Primary));
+        assert_eq!(p.current_position().x, 6.0);
+
+        // A new contact reusing the id keeps the buttons of this frame.
+        s.process_pointer_event(press(4, PointerButton::Primary.into()));
+        let p = s.primary().unwrap();
+        assert!(p.is_active());
+        assert!(p.is_just_released(PointerButton::Primary));
+        assert_eq!(s.len(), 2);
+
+        s.clear_frame();
+        s.process_pointer_event(up(touch(2), 5, 0.0));
+        s.clear_frame();
+        assert_eq!(s.len(), 1);
+        assert!(s.get(PointerId::new(2).unwrap()).is_none());
+        assert!(!s.primary().unwrap().is_just_pressed(PointerButton::Primary));
+    }
+
+    #[test]
+    fn hovering_mouse_survives_up() {
+        let mouse = info(1, PointerType::Mouse);
+        let mut s = PointerStates::default();
+        s.process_pointer_event(PointerEvent::Enter(mouse));
+        s.process_pointer_event(PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: mouse,
+            state: state(1, 0.0, PointerButton::Primary.into()),
+        }));
+        s.process_pointer_event(PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Secondary),
+            pointer: mouse,
+            state: state(2, 0.0, PointerButton::Primary | PointerButton::Secondary),
+        }));
+        s.process_pointer_event(PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: mouse,
+            state: state(3, 0.0, PointerButton::Secondary.into()),
+        }));
+
+        let p = s.primary().unwrap();
+        assert!(p.is_just_pressed(PointerButton::Primary));
+        assert!(p.is_just_pressed(PointerButton::Secondary));
+        assert!(p.is_just_released(PointerButton::Primary));
+        assert!(p.is_down(PointerButton::Secondary));
+        assert!(p.is_pressed());
+
+        s.process_pointer_event(PointerEvent::Up(PointerButtonEvent {
+            button: Some(PointerButton::Secondary),
+            pointer: mouse,
+            state: state(4, 0.0, PointerButtons::default()),
+        }));
+        let p = s.primary().unwrap();
+        assert!(!p.is_pressed());
+        assert!(!p.is_any_down());
+
+        s.process_pointer_event(PointerEvent::Leave(mouse));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn move_starts_tracking_hovering_pointer() {
+        let pen = info(3, PointerType::Pen);
+        let mut s = PointerStates::default();
+        s.process_pointer_event(move_to(pen, 1, 5.0));
+        s.process_pointer_event(down(pen, 2, 6.0));
+        s.process_pointer_event(up(pen, 3, 6.0));
+
+        let p = s.get(PointerId::new(3).unwrap()).unwrap();
+        assert_eq!(p.current_position().x, 6.0);
+        assert!(!p.is_pressed());
+        // The initial state is not coalesced, as it was not a motion.
+        assert_eq!(p.coalesced().len(), 2);
+        assert_eq!(p.motion().x, 1.0);
+    }
+
+    #[test]
+    fn cancel_retires_pointer() {
+        let mut s = PointerStates::default();
+        s.process_pointer_event(down(touch(1), 1, 0.0));
+        s.process_pointer_event(down(touch(2), 1, 0.0));
+        s.process_pointer_event(PointerEvent::Cancel(touch(1)));
+
+        assert!(!s.is_active(PointerId::PRIMARY));
+        assert!(s.is_active(PointerId::new(2).unwrap()));
+        assert!(!s.is_just_released(PointerId::PRIMARY));
+    }
+
+    #[test]
+    fn ignores_events_without_id() {
+        let mut s = PointerStates::default();
+        let anonymous = PointerInfo {
+            pointer_id: None,
+            persistent_device_id: None,
+            pointer_type: PointerType::Unknown,
+        };
+        s.process_pointer_event(down(anonymous, 1, 0.0));
+        s.process_pointer_event(move_to(anonymous, 2, 0.0));
+        s.process_pointer_event(PointerEvent::Enter(anonymous));
+        assert!(s.is_empty());
+        assert!(s.just_pressed().is_empty());
+    }
+}
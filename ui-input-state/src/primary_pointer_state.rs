@@ -5,7 +5,8 @@
 //!
 //! `PrimaryPointerState` maintains the current pointer state along with
 //! per-frame button transitions, coalesced historical states for the current
-//! frame, and any predicted states provided by the backend. Only events from
+//! frame, any predicted states provided by the backend or by an optional
+//! [`MotionPredictor`], and the frame's scrolling and touchpad gestures. Only events from
 //! the primary pointer are processed (see `ui-events` primary pointer semantics).
 //!
 //! Feed it `ui-events` pointer events as they arrive; query it during your
@@ -44,15 +45,20 @@
 //! let lp = ps.current_logical_position();
 //! assert_eq!(lp.x, 5.0);
 //! ```
+//!
+//! [`MotionPredictor`]: ui_events::pointer::MotionPredictor
 extern crate alloc;
 use alloc::vec::Vec;
 
 use ui_events::pointer::{
-    PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerState, PointerUpdate,
+    MotionPredictor, PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerState,
+    PointerUpdate, PredictionConfig,
 };
 
 use dpi::{LogicalPosition, PhysicalPosition};
 
+use crate::{PointerGestureState, ScrollPolicy, ScrollState};
+
 /// A stateful view of the primary pointer.
 #[derive(Clone, Debug, Default)]
 pub struct PrimaryPointerState {
@@ -60,12 +66,26 @@ pub struct PrimaryPointerState {
     just_pressed: PointerButtons,
     /// Buttons that were released during the current frame.
     just_released: PointerButtons,
+    /// Buttons that were held when the pointer was cancelled during the current frame.
+    just_cancelled: PointerButtons,
+    /// The pointer was cancelled during the current frame.
+    cancelled: bool,
+    /// The pointer left the surface during the current frame.
+    left: bool,
+    /// The pointer is over the surface.
+    inside: bool,
     /// Current state.
     current: PointerState,
     /// Coalesced states, ordered by `time`.
     coalesced: Vec<PointerState>,
     /// Predicted states, ordered by `time`.
     predicted: Vec<PointerState>,
+    /// Scrolling during the current frame.
+    scroll: ScrollState,
+    /// Touchpad gestures during the current frame.
+    gesture: PointerGestureState,
+    /// Predictor for moves without predicted states, if enabled.
+    predictor: Option<MotionPredictor>,
 }
 
 impl PrimaryPointerState {
@@ -85,6 +105,41 @@ impl PrimaryPointerState {
         self.just_released.contains(button)
     }
 
+    /// Return `true` if the `button` was held when the pointer was cancelled
+    /// within the last frame.
+    ///
+    /// Cancelled buttons are not reported by [`Self::is_just_released`], so
+    /// that a drag can be rolled back rather than committed.
+    pub fn is_just_cancelled(&self, button: PointerButton) -> bool {
+        self.just_cancelled.contains(button)
+    }
+
+    /// Return `true` if the pointer was cancelled within the last frame.
+    ///
+    /// This corresponds to having received a [`PointerEvent::Cancel`] event
+    /// for the primary pointing device.
+    pub fn is_cancelled_this_frame(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Return `true` if the pointer left the surface within the last frame.
+    ///
+    /// This corresponds to having received a [`PointerEvent::Leave`] event
+    /// for the primary pointing device.
+    pub fn pointer_left_this_frame(&self) -> bool {
+        self.left
+    }
+
+    /// Return `true` if the pointer is over the surface.
+    ///
+    /// This becomes `true` on [`PointerEvent::Enter`], [`PointerEvent::Down`],
+    /// or a move without buttons held, and `false` on [`PointerEvent::Leave`]
+    /// or [`PointerEvent::Cancel`]. Moves with buttons held do not change it,
+    /// as a pointer can keep dragging after it left the surface.
+    pub fn is_inside(&self) -> bool {
+        self.inside
+    }
+
     /// Return `true` if the Auxiliary button (usually middle mouse) was
     /// pressed within the last frame.
     pub fn is_auxiliary_just_pressed(&self) -> bool {
@@ -132,9 +187,38 @@ impl PrimaryPointerState {
     pub fn clear_frame(&mut self) {
         self.just_pressed.clear();
         self.just_released.clear();
+        self.just_cancelled.clear();
+        self.cancelled = false;
+        self.left = false;
         self.coalesced.clear();
-        // TODO: Persist predicted states that are not yet stale.
-        self.predicted.clear();
+        self.retain_predictions();
+        self.scroll.clear_frame();
+        self.gesture.clear_frame();
+    }
+
+    /// Scrolling by the primary pointer during the current frame.
+    pub fn scroll(&self) -> &ScrollState {
+        &self.scroll
+    }
+
+    /// Touchpad gestures by the primary pointer during the current frame.
+    pub fn gesture(&self) -> &PointerGestureState {
+        &self.gesture
+    }
+
+    /// Set how line and page scroll deltas are resolved to pixels in [`Self::scroll`].
+    pub fn set_scroll_policy(&mut self, policy: ScrollPolicy) {
+        self.scroll.set_policy(policy);
+    }
+
+    /// Enable or disable predicting states with a [`MotionPredictor`].
+    ///
+    /// When enabled, moves without predicted states from the backend get
+    /// predictions before being processed, for use with [`Self::predicted_position`].
+    /// Backends that can predict themselves, such as `ui-events-winit`, should
+    /// usually be preferred, as they see every pointer.
+    pub fn set_motion_prediction(&mut self, config: Option<PredictionConfig>) {
+        self.predictor = config.map(MotionPredictor::new);
     }
 
     /// Current position.
@@ -179,6 +263,49 @@ impl PrimaryPointerState {
         }
     }
 
+    /// Best estimate of the position at `time`, in physical pixels.
+    ///
+    /// This interpolates between the current state and the predicted states
+    /// provided by the backend or [`Self::set_motion_prediction`], which are
+    /// kept across frames until the current state catches up with them. For
+    /// times before the current state, or without predictions, this is the
+    /// current position; times after the last prediction give its position,
+    /// without extrapolating.
+    ///
+    /// `time` is in the same time base as [`PointerState::time`]; for
+    /// low-latency drawing, pass the expected presentation time of the frame.
+    pub fn predicted_position(&self, time: u64) -> PhysicalPosition<f64> {
+        let mut before = &self.current;
+        for after in &self.predicted {
+            if after.time >= time {
+                if time <= before.time {
+                    return before.position;
+                }
+                let t = (time - before.time) as f64 / (after.time - before.time) as f64;
+                return PhysicalPosition {
+                    x: before.position.x + (after.position.x - before.position.x) * t,
+                    y: before.position.y + (after.position.y - before.position.y) * t,
+                };
+            }
+            before = after;
+        }
+        before.position
+    }
+
+    /// Drop predicted states that are not later than the current state.
+    fn retain_predictions(&mut self) {
+        let time = self.current.time;
+        self.predicted.retain(|s| s.time > time);
+    }
+
+    /// Drop stale predictions, and give the rest the current buttons.
+    fn propagate_buttons(&mut self) {
+        self.retain_predictions();
+        for s in &mut self.predicted {
+            s.buttons = self.current.buttons;
+        }
+    }
+
     /// Push a state and coalesce the existing one if it is not the initial state.
     fn push_state(&mut self, state: PointerState) {
         if state.time != 0 {
@@ -190,11 +317,17 @@ impl PrimaryPointerState {
     /// Update the state based on the given pointer event.
     ///
     /// Only events from the primary pointer are processed. Press and release
-    /// events update the `just_pressed`, `just_released`, and `down` states.
+    /// events update the `just_pressed`, `just_released`, and `down` states,
+    /// and scroll and gesture events are accumulated into [`Self::scroll`]
+    /// and [`Self::gesture`].
     pub fn process_pointer_event(&mut self, event: PointerEvent) {
         if !event.is_primary_pointer() {
             return;
         }
+        let event = match &mut self.predictor {
+            Some(predictor) => predictor.attach_predictions(event),
+            None => event,
+        };
 
         match event {
             PointerEvent::Down(PointerButtonEvent {
@@ -203,11 +336,11 @@ impl PrimaryPointerState {
                 ..
             }) => {
                 self.just_pressed.insert(b);
+                self.inside = true;
                 let mut state = state.clone();
                 core::mem::swap(&mut self.current, &mut state);
                 self.push_state(state);
-                // TODO: Propagate button state to predicted states.
-                self.predicted.clear();
+                self.propagate_buttons();
             }
             PointerEvent::Up(PointerButtonEvent {
                 button: Some(b),
@@ -218,8 +351,7 @@ impl PrimaryPointerState {
                 let mut state = state.clone();
                 core::mem::swap(&mut self.current, &mut state);
                 self.push_state(state);
-                // TODO: Propagate button state to predicted states.
-                self.predicted.clear();
+                self.propagate_buttons();
             }
             PointerEvent::Move(PointerUpdate {
                 current,
@@ -227,17 +359,43 @@ impl PrimaryPointerState {
                 predicted,
                 ..
             }) => {
+                if current.buttons.is_empty() {
+                    self.inside = true;
+                }
                 self.coalesced.push(self.current.clone());
                 self.current = current.clone();
                 self.coalesced.extend(coalesced);
+                if predicted.is_empty() {
+                    // Older predictions may still be ahead of this state.
+                    self.retain_predictions();
+                } else {
+                    self.predicted = predicted;
+                }
+            }
+            PointerEvent::Scroll(event) => {
+                self.scroll.process_scroll_event(&event);
+            }
+            PointerEvent::Gesture(event) => {
+                self.gesture.process_gesture_event(&event);
+            }
+            PointerEvent::Enter(_) => {
+                self.inside = true;
+            }
+            PointerEvent::Leave(_) => {
+                // Buttons may still be held, for a drag that continues outside.
+                self.left = true;
+                self.inside = false;
                 self.predicted.clear();
-                self.predicted.extend(predicted);
             }
-            PointerEvent::Cancel(_) | PointerEvent::Leave(_) => {
-                // TODO: Validate these behaviors.
+            PointerEvent::Cancel(_) => {
+                // The interaction is abandoned, so held buttons are cancelled
+                // rather than released, and this frame's motion is discarded.
+                self.cancelled = true;
+                self.inside = false;
+                self.just_cancelled.extend(self.current.buttons);
+                self.current.buttons.clear();
                 self.predicted.clear();
                 self.coalesced.clear();
-                self.current.buttons.clear();
             }
             _ => {}
         }
@@ -521,22 +679,62 @@ mod tests {
     }
 
     #[test]
-    fn leave_clears_states() {
+    fn cancel_is_not_a_release() {
         let mut state = PrimaryPointerState::default();
         state.process_pointer_event(make_down_event(PointerButton::Primary));
+        assert!(state.is_inside());
+        state.clear_frame();
 
-        assert!(state.predicted.is_empty());
-        assert!(!state.current.buttons.is_empty());
+        state.process_pointer_event(make_cancel_event());
+
+        assert!(state.is_cancelled_this_frame());
+        assert!(state.is_just_cancelled(PointerButton::Primary));
+        assert!(!state.is_primary_just_released());
+        assert!(!state.is_down(PointerButton::Primary));
+        assert!(!state.is_inside());
+
+        state.clear_frame();
+        assert!(!state.is_cancelled_this_frame());
+        assert!(!state.is_just_cancelled(PointerButton::Primary));
+    }
+
+    #[test]
+    fn leave_keeps_buttons_and_tracks_inside() {
+        let mut state = PrimaryPointerState::default();
+        assert!(!state.is_inside());
+        state.process_pointer_event(PointerEvent::Enter(PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: None,
+            pointer_type: PointerType::Mouse,
+        }));
+        assert!(state.is_inside());
+        state.process_pointer_event(make_down_event(PointerButton::Primary));
 
         state.process_pointer_event(make_leave_event());
 
-        assert!(state.coalesced.is_empty());
+        assert!(state.pointer_left_this_frame());
+        assert!(!state.is_inside());
         assert!(state.predicted.is_empty());
-        assert!(state.current.buttons.is_empty());
+        // A drag can continue outside the surface.
+        assert!(state.is_down(PointerButton::Primary));
+        assert!(!state.is_primary_just_released());
+        assert!(!state.is_cancelled_this_frame());
+
+        state.clear_frame();
+        assert!(!state.pointer_left_this_frame());
+        assert!(!state.is_inside());
+
+        // Moving without buttons means the pointer is back over the surface.
+        state.process_pointer_event(make_move_event(
+            PhysicalPosition { x: 1.0, y: 1.0 },
+            vec![],
+            vec![],
+        ));
+        assert!(state.is_inside());
     }
 
     #[test]
-    fn clear_frame_clears_coalesced_and_predicted() {
+    fn clear_frame_clears_coalesced_and_keeps_future_predictions() {
         let mut state = PrimaryPointerState::default();
 
         state.process_pointer_event(make_move_event(
@@ -551,6 +749,95 @@ mod tests {
         state.clear_frame();
 
         assert!(state.coalesced.is_empty());
+        assert_eq!(state.predicted.len(), 1);
+
+        // A later state makes the prediction stale.
+        state.process_pointer_event(make_move_event(
+            PhysicalPosition { x: 16.0, y: 16.0 },
+            vec![],
+            vec![],
+        ));
+        assert!(state.predicted.is_empty());
+    }
+
+    fn state_at(time: u64, x: f64, buttons: PointerButtons) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y: 0.0 },
+            buttons,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn predictions_follow_buttons_and_interpolate() {
+        let info = PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: None,
+            pointer_type: PointerType::Pen,
+        };
+        let mut state = PrimaryPointerState::default();
+        state.process_pointer_event(PointerEvent::Move(PointerUpdate {
+            pointer: info,
+            current: state_at(100, 10.0, PointerButtons::default()),
+            coalesced: vec![],
+            predicted: vec![
+                state_at(110, 20.0, PointerButtons::default()),
+                state_at(130, 60.0, PointerButtons::default()),
+            ],
+        }));
+
+        assert_eq!(state.predicted_position(50).x, 10.0);
+        assert_eq!(state.predicted_position(100).x, 10.0);
+        assert_eq!(state.predicted_position(105).x, 15.0);
+        assert_eq!(state.predicted_position(120).x, 40.0);
+        assert_eq!(state.predicted_position(200).x, 60.0);
+
+        // A press before the predictions are reached carries into them.
+        state.process_pointer_event(PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info,
+            state: state_at(115, 30.0, PointerButton::Primary.into()),
+        }));
+        assert_eq!(state.predicted.len(), 1);
+        assert!(state.predicted[0].buttons.contains(PointerButton::Primary));
+        assert_eq!(state.predicted_position(120).x, 40.0);
+
+        state.clear_frame();
+        assert_eq!(state.predicted_position(130).x, 60.0);
+    }
+
+    #[test]
+    fn motion_prediction_fills_predicted() {
+        const MS: u64 = 1_000_000;
+        let info = PointerInfo {
+            pointer_id: Some(PointerId::PRIMARY),
+            persistent_device_id: None,
+            pointer_type: PointerType::Pen,
+        };
+        let mut state = PrimaryPointerState::default();
+        state.set_motion_prediction(Some(PredictionConfig::default()));
+        for i in 1..=3 {
+            state.process_pointer_event(PointerEvent::Move(PointerUpdate {
+                pointer: info,
+                current: state_at(i * 8 * MS, i as f64 * 8.0, PointerButtons::default()),
+                coalesced: vec![],
+                predicted: vec![],
+            }));
+        }
+
+        // Moving 1px/ms, predicted 16ms ahead.
+        assert_eq!(state.predicted.len(), 2);
+        assert!((state.predicted_position(32 * MS).x - 32.0).abs() < 1e-6);
+        assert!((state.predicted_position(60 * MS).x - 40.0).abs() < 1e-6);
+
+        state.set_motion_prediction(None);
+        state.process_pointer_event(PointerEvent::Move(PointerUpdate {
+            pointer: info,
+            current: state_at(48 * MS, 48.0, PointerButtons::default()),
+            coalesced: vec![],
+            predicted: vec![],
+        }));
         assert!(state.predicted.is_empty());
     }
 
@@ -618,4 +905,42 @@ mod tests {
         assert_eq!(state.motion(), PhysicalPosition { x: 30.0, y: 40.0 });
         assert_eq!(state.logical_motion(), LogicalPosition { x: 30.0, y: 40.0 });
     }
+
+    #[test]
+    fn scroll_accumulates_per_frame() {
+        use ui_events::pointer::PointerScrollEvent;
+        use ui_events::{ScrollDelta, ScrollPhase, ScrollSource};
+
+        let make_scroll_event = |y| {
+            PointerEvent::Scroll(PointerScrollEvent {
+                pointer: PointerInfo {
+                    pointer_id: Some(PointerId::PRIMARY),
+                    persistent_device_id: None,
+                    pointer_type: PointerType::Mouse,
+                },
+                delta: ScrollDelta::LineDelta(0.0, y),
+                phase: ScrollPhase::Changed,
+                source: ScrollSource::Wheel,
+                state: PointerState {
+                    time: phony_time(),
+                    scale_factor: 1.0,
+                    ..Default::default()
+                },
+            })
+        };
+
+        let mut state = PrimaryPointerState::default();
+        state.set_scroll_policy(
+            ScrollPolicy::default().with_line_size(dpi::LogicalSize::new(10.0, 10.0)),
+        );
+        state.process_pointer_event(make_scroll_event(1.0));
+        state.process_pointer_event(make_scroll_event(2.0));
+        assert!(state.scroll().is_scrolled());
+        assert_eq!(state.scroll().line_delta(), (0.0, 3.0));
+        assert_eq!(state.scroll().delta(), PhysicalPosition { x: 0.0, y: 30.0 });
+
+        state.clear_frame();
+        assert!(!state.scroll().is_scrolled());
+        assert_eq!(state.scroll().policy().line_size.height, 10.0);
+    }
 }
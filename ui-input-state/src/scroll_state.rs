@@ -0,0 +1,242 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Per-frame scroll accumulation.
+//!
+//! [`ScrollState`] sums the [`PointerScrollEvent`]s of a frame, both as raw
+//! per-variant totals and as a single pixel delta resolved through a
+//! [`ScrollPolicy`]. [`PrimaryPointerState`] keeps one for the primary pointer,
+//! so scrolling can be read alongside the rest of the pointer state.
+//!
+//! [`PrimaryPointerState`]: crate::PrimaryPointerState
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::{PrimaryPointerState, ScrollPolicy};
+//! use ui_events::pointer::PointerEvent;
+//! use dpi::LogicalSize;
+//!
+//! let mut ps = PrimaryPointerState::default();
+//! // Scroll by a third of a 600px tall viewport per line.
+//! ps.set_scroll_policy(ScrollPolicy::default().with_line_size(LogicalSize::new(40.0, 200.0)));
+//! # let events: Vec<PointerEvent> = unimplemented!();
+//! for e in events {
+//!     ps.process_pointer_event(e);
+//! }
+//! let scroll = ps.scroll().logical_delta();
+//! let _ = (scroll.x, scroll.y);
+//! ps.clear_frame();
+//! ```
+
+use ui_events::ScrollDelta;
+use ui_events::pointer::PointerScrollEvent;
+
+use dpi::{LogicalPosition, LogicalSize, PhysicalPosition};
+
+/// How [`ScrollDelta::LineDelta`] and [`ScrollDelta::PageDelta`] are resolved to pixels.
+///
+/// Sizes are in logical pixels, and are converted using each event's `scale_factor`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollPolicy {
+    /// Distance scrolled by one line, on each axis.
+    pub line_size: LogicalSize<f64>,
+    /// Distance scrolled by one page, on each axis.
+    ///
+    /// This is usually the size of the scrolled viewport.
+    pub page_size: LogicalSize<f64>,
+}
+
+impl Default for ScrollPolicy {
+    fn default() -> Self {
+        Self {
+            line_size: LogicalSize::new(40.0, 40.0),
+            page_size: LogicalSize::new(800.0, 800.0),
+        }
+    }
+}
+
+impl ScrollPolicy {
+    /// Set the line size (builder style).
+    pub fn with_line_size(mut self, line_size: LogicalSize<f64>) -> Self {
+        self.line_size = line_size;
+        self
+    }
+
+    /// Set the page size (builder style).
+    pub fn with_page_size(mut self, page_size: LogicalSize<f64>) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Resolve `delta` to physical pixels, with [`ScrollDelta::to_pixel_delta`].
+    pub fn to_pixel_delta(&self, delta: ScrollDelta, scale_factor: f64) -> PhysicalPosition<f64> {
+        let line = self.line_size.to_physical::<f64>(scale_factor);
+        let page = self.page_size.to_physical::<f64>(scale_factor);
+        delta.to_pixel_delta(
+            PhysicalPosition::new(line.width, line.height),
+            PhysicalPosition::new(page.width, page.height),
+        )
+    }
+}
+
+/// Scrolling accumulated over a frame.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollState {
+    /// Policy for resolving lines and pages.
+    policy: ScrollPolicy,
+    /// Resolved total, in physical pixels.
+    delta: PhysicalPosition<f64>,
+    /// Sum of line deltas.
+    lines: (f32, f32),
+    /// Sum of page deltas.
+    pages: (f32, f32),
+    /// Sum of pixel deltas.
+    pixels: PhysicalPosition<f64>,
+    /// Number of scroll events this frame.
+    events: usize,
+    /// Scale factor of the latest scroll event.
+    scale_factor: f64,
+}
+
+impl ScrollState {
+    /// Create a scroll state with the given policy.
+    pub fn new(policy: ScrollPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// The policy for resolving lines and pages.
+    pub fn policy(&self) -> &ScrollPolicy {
+        &self.policy
+    }
+
+    /// Set the policy for resolving lines and pages.
+    ///
+    /// This applies to scroll events processed after the call.
+    pub fn set_policy(&mut self, policy: ScrollPolicy) {
+        self.policy = policy;
+    }
+
+    /// Return `true` if there was any scroll event this frame.
+    pub fn is_scrolled(&self) -> bool {
+        self.events != 0
+    }
+
+    /// Total scroll this frame, in physical pixels.
+    ///
+    /// Line and page deltas are resolved through the [`ScrollPolicy`].
+    pub fn delta(&self) -> PhysicalPosition<f64> {
+        self.delta
+    }
+
+    /// Total scroll this frame, in logical pixels.
+    ///
+    /// This uses the scale factor of the latest scroll event.
+    pub fn logical_delta(&self) -> LogicalPosition<f64> {
+        if self.events == 0 {
+            return LogicalPosition::default();
+        }
+        self.delta.to_logical(self.scale_factor)
+    }
+
+    /// Sum of the [`ScrollDelta::LineDelta`]s this frame.
+    pub fn line_delta(&self) -> (f32, f32) {
+        self.lines
+    }
+
+    /// Sum of the [`ScrollDelta::PageDelta`]s this frame.
+    pub fn page_delta(&self) -> (f32, f32) {
+        self.pages
+    }
+
+    /// Sum of the [`ScrollDelta::PixelDelta`]s this frame.
+    pub fn pixel_delta(&self) -> PhysicalPosition<f64> {
+        self.pixels
+    }
+
+    /// Add a scroll event to the totals.
+    pub fn process_scroll_event(&mut self, event: &PointerScrollEvent) {
+        let scale_factor = event.state.scale_factor;
+        match event.delta {
+            ScrollDelta::LineDelta(x, y) => {
+                self.lines.0 += x;
+                self.lines.1 += y;
+            }
+            ScrollDelta::PageDelta(x, y) => {
+                self.pages.0 += x;
+                self.pages.1 += y;
+            }
+            ScrollDelta::PixelDelta(p) => {
+                self.pixels.x += p.x;
+                self.pixels.y += p.y;
+            }
+        }
+        let delta = self.policy.to_pixel_delta(event.delta, scale_factor);
+        self.delta.x += delta.x;
+        self.delta.y += delta.y;
+        self.events += 1;
+        self.scale_factor = scale_factor;
+    }
+
+    /// Clear the per-frame totals to prepare for a new frame.
+    pub fn clear_frame(&mut self) {
+        *self = Self::new(self.policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ui_events::pointer::{PointerId, PointerInfo, PointerState, PointerType};
+    use ui_events::{ScrollPhase, ScrollSource};
+
+    fn scroll(delta: ScrollDelta, scale_factor: f64) -> PointerScrollEvent {
+        PointerScrollEvent {
+            pointer: PointerInfo {
+                pointer_id: Some(PointerId::PRIMARY),
+                persistent_device_id: None,
+                pointer_type: PointerType::Mouse,
+            },
+            delta,
+            phase: ScrollPhase::Changed,
+            source: ScrollSource::Unknown,
+            state: PointerState {
+                scale_factor,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn sums_and_resolves_each_variant() {
+        let policy = ScrollPolicy::default()
+            .with_line_size(LogicalSize::new(10.0, 20.0))
+            .with_page_size(LogicalSize::new(300.0, 400.0));
+        let mut s = ScrollState::new(policy);
+        assert!(!s.is_scrolled());
+
+        s.process_scroll_event(&scroll(ScrollDelta::LineDelta(1.0, 2.0), 2.0));
+        s.process_scroll_event(&scroll(ScrollDelta::LineDelta(0.0, -1.0), 2.0));
+        s.process_scroll_event(&scroll(ScrollDelta::PageDelta(0.0, 0.5), 2.0));
+        s.process_scroll_event(&scroll(
+            ScrollDelta::PixelDelta(PhysicalPosition::new(3.0, 4.0)),
+            2.0,
+        ));
+
+        assert!(s.is_scrolled());
+        assert_eq!(s.line_delta(), (1.0, 1.0));
+        assert_eq!(s.page_delta(), (0.0, 0.5));
+        assert_eq!(s.pixel_delta(), PhysicalPosition::new(3.0, 4.0));
+        // Lines: (20, 40), page: (0, 400), pixels: (3, 4).
+        assert_eq!(s.delta(), PhysicalPosition::new(23.0, 444.0));
+        assert_eq!(s.logical_delta(), LogicalPosition::new(11.5, 222.0));
+
+        s.clear_frame();
+        assert!(!s.is_scrolled());
+        assert_eq!(s.delta(), PhysicalPosition::default());
+        assert_eq!(s.policy(), &policy);
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright 2025 the UI Events Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! # Estimating pointer velocity and acceleration.
+//!
+//! [`VelocityTracker`] keeps a [`MotionHistory`] of positions for one pointer,
+//! taken from the current and coalesced states of each [`PointerUpdate`],
+//! and fits a least-squares polynomial to them, like Android's
+//! `VelocityTracker`. The fit is quadratic when there are at least three
+//! samples, so the estimate includes acceleration, and linear with two.
+//!
+//! The history is robust to the ways real event streams misbehave:
+//!
+//! - Samples older than [`VelocityTrackerConfig::horizon`] are dropped.
+//! - A gap longer than [`VelocityTrackerConfig::max_gap`] between samples
+//!   means the pointer stopped, so older history is discarded.
+//! - A sample with the same timestamp as the previous one replaces it,
+//!   and samples that go back in time are ignored.
+//! - [`PointerEvent::Cancel`] clears the history.
+//!
+//! ## Example:
+//!
+//! ```no_run
+//! use ui_input_state::VelocityTracker;
+//! use ui_events::pointer::PointerEvent;
+//!
+//! let mut tracker = VelocityTracker::default();
+//! # let events: Vec<PointerEvent> = unimplemented!();
+//! for e in &events {
+//!     tracker.process_pointer_event(e);
+//!     if let PointerEvent::Up(_) = e {
+//!         if let Some(estimate) = tracker.estimate() {
+//!             // Start a fling with this velocity, in logical pixels per second.
+//!             let v = estimate.logical_velocity();
+//!             let _ = (v.x, v.y);
+//!         }
+//!     }
+//! }
+//! ```
+use ui_events::pointer::{
+    MotionHistory, PointerButtonEvent, PointerEvent, PointerId, PointerState, PointerUpdate,
+};
+
+use dpi::{LogicalPosition, PhysicalPosition};
+
+/// Configuration for a [`VelocityTracker`].
+///
+/// Times are in nanoseconds.
+#[derive(Clone, Copy, Debug)]
+pub struct VelocityTrackerConfig {
+    /// How far back from the newest sample samples are considered.
+    pub horizon: u64,
+    /// Longest time between two samples before the pointer is considered
+    /// to have stopped, discarding the older history.
+    pub max_gap: u64,
+    /// Maximum number of samples kept.
+    pub max_samples: usize,
+}
+
+impl Default for VelocityTrackerConfig {
+    fn default() -> Self {
+        Self {
+            horizon: 100_000_000,
+            max_gap: 40_000_000,
+            max_samples: 20,
+        }
+    }
+}
+
+impl VelocityTrackerConfig {
+    /// Set the history horizon (builder style).
+    pub fn with_horizon(mut self, horizon: u64) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Set the maximum gap between samples (builder style).
+    pub fn with_max_gap(mut self, max_gap: u64) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Set the maximum number of samples (builder style).
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+}
+
+/// Velocity and acceleration estimated by a [`VelocityTracker`].
+///
+/// These are in physical pixels per second, and per second squared,
+/// at the time of the newest sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VelocityEstimate {
+    /// Estimated velocity.
+    pub velocity: PhysicalPosition<f64>,
+    /// Estimated acceleration.
+    ///
+    /// This is zero when the estimate is based on only two samples.
+    pub acceleration: PhysicalPosition<f64>,
+    /// Scale factor of the newest sample.
+    pub scale_factor: f64,
+}
+
+impl VelocityEstimate {
+    /// Velocity in logical pixels per second.
+    pub fn logical_velocity(&self) -> LogicalPosition<f64> {
+        self.velocity.to_logical(self.scale_factor)
+    }
+
+    /// Acceleration in logical pixels per second squared.
+    pub fn logical_acceleration(&self) -> LogicalPosition<f64> {
+        self.acceleration.to_logical(self.scale_factor)
+    }
+}
+
+/// Estimates the velocity and acceleration of a single pointer.
+#[derive(Clone, Debug)]
+pub struct VelocityTracker {
+    /// Configuration.
+    config: VelocityTrackerConfig,
+    /// The pointer being tracked.
+    pointer_id: Option<PointerId>,
+    /// Recent samples.
+    history: MotionHistory,
+    /// Scale factor of the newest sample.
+    scale_factor: f64,
+}
+
+impl Default for VelocityTracker {
+    fn default() -> Self {
+        Self::new(VelocityTrackerConfig::default())
+    }
+}
+
+impl VelocityTracker {
+    /// Create a tracker with the given configuration.
+    pub fn new(config: VelocityTrackerConfig) -> Self {
+        Self {
+            config,
+            pointer_id: None,
+            history: MotionHistory::default()
+                .with_horizon(config.horizon)
+                .with_max_gap(config.max_gap)
+                .with_max_samples(config.max_samples),
+            scale_factor: 1.0,
+        }
+    }
+
+    /// The configuration.
+    pub fn config(&self) -> &VelocityTrackerConfig {
+        &self.config
+    }
+
+    /// Forget all samples and the tracked pointer.
+    pub fn reset(&mut self) {
+        self.pointer_id = None;
+        self.history.clear();
+    }
+
+    /// Update the tracker with an event.
+    ///
+    /// The tracker follows the pointer of the first event it sees, or of the
+    /// latest [`PointerEvent::Down`]; events from other pointers are ignored.
+    /// A `Down` starts a new history, and a [`PointerEvent::Cancel`] clears it.
+    pub fn process_pointer_event(&mut self, event: &PointerEvent) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent { pointer, state, .. }) => {
+                self.reset();
+                self.pointer_id = pointer.pointer_id;
+                self.add_state(state);
+            }
+            PointerEvent::Move(update) if self.follows(update.pointer.pointer_id) => {
+                self.process_pointer_update(update);
+            }
+            PointerEvent::Up(PointerButtonEvent { pointer, state, .. })
+                if self.follows(pointer.pointer_id) =>
+            {
+                self.add_state(state);
+            }
+            PointerEvent::Cancel(pointer) if self.follows(pointer.pointer_id) => {
+                self.reset();
+            }
+            _ => {}
+        }
+    }
+
+    /// Add the coalesced and current states of an update.
+    ///
+    /// This does not check which pointer the update is for.
+    pub fn process_pointer_update(&mut self, update: &PointerUpdate) {
+        for state in update
+            .coalesced
+            .iter()
+            .chain(core::iter::once(&update.current))
+        {
+            self.add_state(state);
+        }
+    }
+
+    /// Add a single position sample, in physical pixels.
+    ///
+    /// `time` is in the same time base as [`PointerState::time`].
+    /// Samples added this way take the scale factor of the last processed
+    /// event, which is 1 until one is processed.
+    pub fn add_sample(&mut self, time: u64, position: PhysicalPosition<f64>) {
+        self.history.add_sample(time, position);
+    }
+
+    /// Estimate velocity and acceleration at the newest sample.
+    ///
+    /// Returns `None` with fewer than two samples in the history.
+    pub fn estimate(&self) -> Option<VelocityEstimate> {
+        let (velocity, acceleration) = self.history.velocity_and_acceleration()?;
+        Some(VelocityEstimate {
+            velocity,
+            acceleration,
+            scale_factor: self.scale_factor,
+        })
+    }
+
+    fn follows(&self, pointer_id: Option<PointerId>) -> bool {
+        self.pointer_id.is_none() || self.pointer_id == pointer_id
+    }
+
+    fn add_state(&mut self, state: &PointerState) {
+        self.scale_factor = state.scale_factor;
+        self.add_sample(state.time, state.position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use ui_events::pointer::{PointerButton, PointerInfo, PointerType};
+
+    const MS: u64 = 1_000_000;
+
+    fn info(id: u64) -> PointerInfo {
+        PointerInfo {
+            pointer_id: PointerId::new(id),
+            persistent_device_id: None,
+            pointer_type: PointerType::Touch,
+        }
+    }
+
+    fn state(time: u64, x: f64, y: f64) -> PointerState {
+        PointerState {
+            time,
+            position: PhysicalPosition { x, y },
+            scale_factor: 2.0,
+            ..Default::default()
+        }
+    }
+
+    fn down(id: u64, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            pointer: info(id),
+            state: state(time, x, y),
+        })
+    }
+
+    fn move_to(id: u64, time: u64, x: f64, y: f64) -> PointerEvent {
+        PointerEvent::Move(PointerUpdate {
+            pointer: info(id),
+            current: state(time, x, y),
+            coalesced: vec![],
+            predicted: vec![],
+        })
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "expected {a} ~= {b}");
+    }
+
+    #[test]
+    fn constant_velocity() {
+        let mut t = VelocityTracker::default();
+        t.process_pointer_event(&down(1, 0, 0.0, 0.0));
+        assert_eq!(t.estimate(), None);
+        for i in 1..=5 {
+            t.process_pointer_event(&move_to(1, i * 8 * MS, i as f64 * 8.0, i as f64 * -4.0));
+        }
+        let e = t.estimate().unwrap();
+        assert_approx(e.velocity.x, 1_000.0);
+        assert_approx(e.velocity.y, -500.0);
+        assert_approx(e.acceleration.x, 0.0);
+        assert_approx(e.logical_velocity().x, 500.0);
+    }
+
+    #[test]
+    fn constant_acceleration() {
+        // x = 0.5 a t², with a = 2000 px/s² and t in seconds.
+        let mut t = VelocityTracker::default();
+        for i in 0..6_u64 {
+            let secs = (i * 10) as f64 * 1e-3;
+            t.add_sample(
+                i * 10 * MS,
+                PhysicalPosition::new(1_000.0 * secs * secs, 0.0),
+            );
+        }
+        let e = t.estimate().unwrap();
+        assert_approx(e.acceleration.x, 2_000.0);
+        // v = a t at t = 50ms.
+        assert_approx(e.velocity.x, 100.0);
+    }
+
+    #[test]
+    fn samples_without_events_are_logical() {
+        let mut t = VelocityTracker::default();
+        t.add_sample(0, PhysicalPosition::new(0.0, 0.0));
+        t.add_sample(10 * MS, PhysicalPosition::new(10.0, 0.0));
+        let e = t.estimate().unwrap();
+        assert_eq!(e.scale_factor, 1.0);
+        assert_approx(e.logical_velocity().x, 1_000.0);
+        assert_approx(e.logical_acceleration().x, 0.0);
+    }
+
+    #[test]
+    fn coalesced_states_are_used() {
+        let mut t = VelocityTracker::default();
+        t.process_pointer_event(&down(1, 0, 0.0, 0.0));
+        t.process_pointer_event(&PointerEvent::Move(PointerUpdate {
+            pointer: info(1),
+            current: state(20 * MS, 20.0, 0.0),
+            coalesced: vec![state(10 * MS, 10.0, 0.0)],
+            predicted: vec![],
+        }));
+        assert_approx(t.estimate().unwrap().velocity.x, 1_000.0);
+    }
+
+    #[test]
+    fn duplicate_and_out_of_order_timestamps() {
+        let mut t = VelocityTracker::default();
+        t.add_sample(0, PhysicalPosition::new(0.0, 0.0));
+        t.add_sample(10 * MS, PhysicalPosition::new(99.0, 0.0));
+        // Replaces the previous sample.
+        t.add_sample(10 * MS, PhysicalPosition::new(10.0, 0.0));
+        // Ignored.
+        t.add_sample(5 * MS, PhysicalPosition::new(-50.0, 0.0));
+        t.add_sample(20 * MS, PhysicalPosition::new(20.0, 0.0));
+        let e = t.estimate().unwrap();
+        assert!(e.velocity.x.is_finite());
+        assert_approx(e.velocity.x, 1_000.0);
+    }
+
+    #[test]
+    fn gaps_and_horizon_discard_history() {
+        let mut t = VelocityTracker::default();
+        t.process_pointer_event(&down(1, 0, 0.0, 0.0));
+        t.process_pointer_event(&move_to(1, 10 * MS, 100.0, 0.0));
+        // The pointer rested for longer than `max_gap` before moving again.
+        t.process_pointer_event(&move_to(1, 200 * MS, 100.0, 0.0));
+        assert_eq!(t.estimate(), None);
+        t.process_pointer_event(&move_to(1, 210 * MS, 110.0, 0.0));
+        assert_approx(t.estimate().unwrap().velocity.x, 1_000.0);
+
+        let mut t = VelocityTracker::new(VelocityTrackerConfig::default().with_horizon(25 * MS));
+        for i in 0..10_u64 {
+            // Slow, then fast.
+            let x = if i < 5 {
+                i as f64
+            } else {
+                5.0 + (i - 5) as f64 * 10.0
+            };
+            t.add_sample(i * 10 * MS, PhysicalPosition::new(x, 0.0));
+        }
+        assert_approx(t.estimate().unwrap().velocity.x, 1_000.0);
+    }
+
+    #[test]
+    fn cancel_and_other_pointers() {
+        let mut t = VelocityTracker::default();
+        t.process_pointer_event(&down(1, 0, 0.0, 0.0));
+        t.process_pointer_event(&move_to(2, 10 * MS, 500.0, 0.0));
+        t.process_pointer_event(&move_to(1, 10 * MS, 10.0, 0.0));
+        assert_approx(t.estimate().unwrap().velocity.x, 1_000.0);
+
+        t.process_pointer_event(&PointerEvent::Cancel(info(1)));
+        assert_eq!(t.estimate(), None);
+
+        // A new pointer starts a fresh history.
+        t.process_pointer_event(&down(3, 20 * MS, 0.0, 0.0));
+        t.process_pointer_event(&move_to(3, 30 * MS, 0.0, 5.0));
+        assert_approx(t.estimate().unwrap().velocity.y, 500.0);
+    }
+}